@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use crate::challenges::{Challenges, InputSource, Part, SolveOptions};
+
+type Error = String;
+
+/// Known-correct answers, loaded from a JSON file of the form
+/// `{"1": {"part1": 280, "part2": 1797}, "11": {"part1": "vzbxxyzz"}}`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    answers_by_day: BTreeMap<u8, [Option<String>; 2]>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let contents = fs::read_to_string(path)
+            .map_err(|error| format!("could not read {}: {}", path.display(), error))?;
+        contents
+            .parse()
+            .map_err(|error| format!("invalid answers file {}: {}", path.display(), error))
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        let answers = self.answers_by_day.get(&day)?;
+        let answer = match part {
+            Part::One => &answers[0],
+            Part::Two => &answers[1],
+        };
+        answer.as_deref()
+    }
+
+    /// How many parts fail when a whole day fails: the requested ones, only counting those with an
+    /// answer so that e.g. day 25 doesn't fail twice. At least one
+    fn failed_parts_on_error(&self, day: u8, part: Option<Part>) -> usize {
+        let requested_parts = match part {
+            Some(part) => vec![part],
+            None => vec![Part::One, Part::Two],
+        };
+        requested_parts
+            .into_iter()
+            .filter(|&part| self.get(day, part).is_some())
+            .count()
+            .max(1)
+    }
+}
+
+impl FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value: Value = serde_json::from_str(s).map_err(|error| error.to_string())?;
+        let days = value.as_object().ok_or("expected an object keyed by day")?;
+        let mut answers_by_day = BTreeMap::new();
+        for (day, parts) in days {
+            let day = day
+                .parse::<u8>()
+                .map_err(|_| format!("invalid day: {:?}", day))?;
+            let parts = parts
+                .as_object()
+                .ok_or_else(|| format!("day {}: expected an object with part1/part2", day))?;
+            let mut answers = [None, None];
+            for (part, answer) in parts {
+                let index = match part.as_str() {
+                    "part1" => 0,
+                    "part2" => 1,
+                    _ => return Err(format!("day {}: unknown part {:?}", day, part)),
+                };
+                answers[index] = Some(match answer {
+                    Value::String(answer) => answer.clone(),
+                    Value::Number(answer) => answer.to_string(),
+                    _ => {
+                        return Err(format!(
+                            "day {}, {}: expected a string or number",
+                            day, part
+                        ))
+                    }
+                });
+            }
+            answers_by_day.insert(day, answers);
+        }
+        Ok(Self { answers_by_day })
+    }
+}
+
+/// Compares the solutions of the given days against the known answers, returns whether no part
+/// failed or gave a wrong answer. Parts without a known answer are reported as missing
+pub fn verify_days(
+    challenges: &Challenges,
+    days: &[u8],
+    input: &InputSource,
+    options: &SolveOptions,
+    jobs: usize,
+    answers: &Answers,
+) -> bool {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    challenges.solve_days(days, input, options, jobs, |day, outcome| {
+        let solutions = match outcome {
+            Ok(solutions) => solutions,
+            Err(error) => {
+                println!("day {}: error", day);
+                eprintln!("error: {}", error);
+                failed += answers.failed_parts_on_error(day, options.part);
+                return;
+            }
+        };
+        for (part, solution) in solutions.parts() {
+            match (&solution.answer, answers.get(day, part)) {
+                (Err(error), _) => {
+                    println!("day {}, {}: error", day, part);
+                    eprintln!("error: {}", error);
+                    failed += 1;
+                }
+                (Ok(solution), None) => {
+                    println!("day {}, {}: missing (got {})", day, part, solution);
+                    missing += 1;
+                }
+                (Ok(solution), Some(answer)) if solution == answer => {
+                    println!("day {}, {}: pass", day, part);
+                    passed += 1;
+                }
+                (Ok(solution), Some(answer)) => {
+                    println!(
+                        "day {}, {}: FAIL (expected {}, got {})",
+                        day, part, answer, solution
+                    );
+                    failed += 1;
+                }
+            }
+        }
+    });
+    println!();
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    failed == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answers_parsing() {
+        let answers: Answers =
+            r#"{"1": {"part1": 280, "part2": "1797"}, "11": {"part1": "vzbxxyzz"}}"#
+                .parse()
+                .unwrap();
+        assert_eq!(answers.get(1, Part::One), Some("280"));
+        assert_eq!(answers.get(1, Part::Two), Some("1797"));
+        assert_eq!(answers.get(11, Part::One), Some("vzbxxyzz"));
+        assert_eq!(answers.get(11, Part::Two), None);
+        assert_eq!(answers.get(2, Part::One), None);
+
+        assert!(r#"{"x": {}}"#.parse::<Answers>().is_err());
+        assert!(r#"{"1": {"part3": 1}}"#.parse::<Answers>().is_err());
+        assert!(r#"{"1": {"part1": [1]}}"#.parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn test_failed_parts_on_error() {
+        let answers: Answers = r#"{"1": {"part1": 280, "part2": 1797}, "25": {"part1": 1}}"#
+            .parse()
+            .unwrap();
+        assert_eq!(answers.failed_parts_on_error(1, None), 2);
+        assert_eq!(answers.failed_parts_on_error(1, Some(Part::Two)), 1);
+        assert_eq!(answers.failed_parts_on_error(25, None), 1);
+        assert_eq!(answers.failed_parts_on_error(25, Some(Part::Two)), 1);
+        assert_eq!(answers.failed_parts_on_error(19, None), 1);
+    }
+}
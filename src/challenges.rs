@@ -1,41 +1,198 @@
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-
-use std::collections::HashMap;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod parsing;
+
+use std::cell::Cell;
+use std::collections::BTreeMap;
 use std::fmt::{self, Display};
 use std::fs;
+use std::io::{self, Read};
+use std::panic;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::params::Params;
 
-pub trait Challenge {
+pub trait Challenge: Sized {
     const DAY: u8;
     type Part1Solution: Display;
     type Part2Solution: Display;
 
-    fn new(input: &str) -> Self;
-    fn solve_part1(&self) -> Self::Part1Solution;
-    fn solve_part2(&self) -> Self::Part2Solution;
+    /// Names of the puzzle constants this day reads from [`Params`]
+    const PARAMS: &'static [&'static str] = &[];
+
+    fn new(input: &str) -> ChallengeResult<Self>;
+
+    /// Like [`Challenge::new`], but with some of the puzzle constants overridden. Only needs to be
+    /// implemented by days that declare [`Challenge::PARAMS`]
+    fn with_params(input: &str, _params: &Params) -> ChallengeResult<Self> {
+        Self::new(input)
+    }
+
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution>;
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution>;
+}
+
+/// Optional extension of [`Challenge`] for days that can describe how they reached their answers,
+/// e.g. the winning spell sequence of day 22. Returns the answer along with the explanation, both
+/// from the same search, so that explaining doesn't solve the part twice
+pub trait Explain: Challenge {
+    fn explain_part1(&self) -> ChallengeResult<(Self::Part1Solution, String)>;
+    fn explain_part2(&self) -> ChallengeResult<(Self::Part2Solution, String)>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "part 1"),
+            Part::Two => write!(f, "part 2"),
+        }
+    }
+}
+
+/// Position in the puzzle input, both 1-based
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChallengeError {
+    day: Option<u8>,
+    part: Option<Part>,
+    location: Option<Location>,
+    message: String,
+}
+
+pub type ChallengeResult<T> = Result<T, ChallengeError>;
+
+impl ChallengeError {
+    pub fn new(message: impl Display) -> Self {
+        Self {
+            day: None,
+            part: None,
+            location: None,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn at_line(line: usize, message: impl Display) -> Self {
+        Self {
+            location: Some(Location { line, column: None }),
+            ..Self::new(message)
+        }
+    }
+
+    pub fn at(line: usize, column: usize, message: impl Display) -> Self {
+        Self {
+            location: Some(Location {
+                line,
+                column: Some(column),
+            }),
+            ..Self::new(message)
+        }
+    }
+
+    fn in_day(self, day: u8) -> Self {
+        Self {
+            day: Some(day),
+            ..self
+        }
+    }
+
+    fn in_part(self, part: Part) -> Self {
+        Self {
+            part: Some(part),
+            ..self
+        }
+    }
+}
+
+impl Display for ChallengeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut context = Vec::new();
+        if let Some(day) = self.day {
+            context.push(format!("day {}", day));
+        }
+        if let Some(part) = self.part {
+            context.push(part.to_string());
+        }
+        if let Some(location) = self.location {
+            context.push(format!("line {}", location.line));
+            if let Some(column) = location.column {
+                context.push(format!("column {}", column));
+            }
+        }
+
+        if context.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", context.join(", "), self.message)
+        }
+    }
+}
+
+impl std::error::Error for ChallengeError {}
+
+/// An error from parsing a single line, which may also know the column it happened at
+pub trait LineError: Display + Sized {
+    fn at_line(self, line: usize) -> ChallengeError {
+        ChallengeError::at_line(line, self)
+    }
+}
+
+impl LineError for String {}
+impl LineError for Box<dyn std::error::Error> {}
+impl LineError for std::num::ParseIntError {}
+
+/// Parses every line of the input, reporting the location of the first line that fails
+pub fn parse_lines<T>(input: &str) -> ChallengeResult<Vec<T>>
+where
+    T: FromStr,
+    T::Err: LineError,
+{
+    input
+        .lines()
+        .zip(1..)
+        .map(|(line, line_number)| {
+            line.parse::<T>()
+                .map_err(|error| error.at_line(line_number))
+        })
+        .collect()
 }
 
 pub struct NotImplemented;
@@ -45,28 +202,294 @@ impl Display for NotImplemented {
     }
 }
 
-struct FormattedSolutions {
-    part1: String,
-    part2: String,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveOptions {
+    /// How many times to run each phase; timings report min, median and max over the runs
+    pub repetitions: usize,
+    /// Solve only this part, or both if None
+    pub part: Option<Part>,
+    /// Also explain the answers, for days that implement [`Explain`]
+    pub explain: bool,
+    /// Overrides for the puzzle constants of the solved days
+    pub params: Params,
+}
+
+impl SolveOptions {
+    pub fn includes(&self, part: Part) -> bool {
+        self.part.is_none_or(|only_part| only_part == part)
+    }
+}
+
+impl Default for SolveOptions {
+    fn default() -> Self {
+        Self {
+            repetitions: 1,
+            part: None,
+            explain: false,
+            params: Params::default(),
+        }
+    }
+}
+
+/// Run time of one phase (parsing or solving a part) over one or more repetitions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub repetitions: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timing {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Self {
+            repetitions: samples.len(),
+            min: samples.first().copied().unwrap_or_default(),
+            median: samples.get(samples.len() / 2).copied().unwrap_or_default(),
+            max: samples.last().copied().unwrap_or_default(),
+        }
+    }
+}
+
+impl Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.repetitions > 1 {
+            write!(
+                f,
+                "min {:.2?}, median {:.2?}, max {:.2?}",
+                self.min, self.median, self.max
+            )
+        } else {
+            write!(f, "{:.2?}", self.median)
+        }
+    }
+}
+
+/// Runs `phase` up to `repetitions` times, stopping at the first error, and returns the last
+/// result along with the timing of all runs
+fn measure<T>(
+    repetitions: usize,
+    mut phase: impl FnMut() -> ChallengeResult<T>,
+) -> (ChallengeResult<T>, Timing) {
+    let mut samples = Vec::with_capacity(repetitions);
+    loop {
+        let start = Instant::now();
+        let result = phase();
+        samples.push(start.elapsed());
+        if result.is_err() || samples.len() >= repetitions {
+            return (result, Timing::from_samples(samples));
+        }
+    }
+}
+
+/// The outcome of one part: its formatted answer and how long solving it took
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartSolution {
+    pub answer: ChallengeResult<String>,
+    pub timing: Timing,
+    /// Only present if requested and the day implements [`Explain`]
+    pub explanation: Option<ChallengeResult<String>>,
+}
+
+/// The outcome of solving a day whose input could be parsed. Parts that were not requested are
+/// None
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySolutions {
+    pub parse_timing: Timing,
+    pub part1: Option<PartSolution>,
+    pub part2: Option<PartSolution>,
+}
+
+impl DaySolutions {
+    /// The parts that were solved
+    pub fn parts(&self) -> impl Iterator<Item = (Part, &PartSolution)> {
+        [(Part::One, &self.part1), (Part::Two, &self.part2)]
+            .into_iter()
+            .filter_map(|(part, solution)| Some((part, solution.as_ref()?)))
+    }
+
+    pub fn part(&self, part: Part) -> Option<&PartSolution> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+
+    pub fn first_error(&self) -> Option<&ChallengeError> {
+        self.parts()
+            .find_map(|(_, solution)| solution.answer.as_ref().err())
+    }
+
+    /// Sum of the median times of all phases
+    pub fn total_time(&self) -> Duration {
+        self.parse_timing.median
+            + self
+                .parts()
+                .map(|(_, solution)| solution.timing.median)
+                .sum::<Duration>()
+    }
+}
+
+/// Where the puzzle input of a day is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// A directory holding one `dayNN.txt` file per day
+    Directory(PathBuf),
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// The file holding the input of `day`, None for stdin
+    pub fn path(&self, day: u8) -> Option<PathBuf> {
+        match self {
+            InputSource::Directory(directory) => Some(directory.join(format!("day{:02}.txt", day))),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    pub fn read(&self, day: u8) -> ChallengeResult<String> {
+        let Some(path) = self.path(day) else {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|error| ChallengeError::new(format!("could not read stdin: {}", error)))?;
+            return Ok(input);
+        };
+        fs::read_to_string(&path).map_err(|error| {
+            ChallengeError::new(format!("could not read {}: {}", path.display(), error))
+        })
+    }
+}
+
+type FormatSolutionsFn = fn(input: &str, options: &SolveOptions) -> ChallengeResult<DaySolutions>;
+
+const SOLVER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+thread_local! {
+    /// Whether this thread is running a solver, whose panics are reported as errors instead
+    static SOLVING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `solve`, catching a panic without printing it. The panic hook is process-wide, so it's
+/// replaced once by one that stays quiet only on threads that are inside a solver
+fn catch_solver_panic<T>(solve: impl FnOnce() -> T) -> Result<T, String> {
+    static QUIET_HOOK: Once = Once::new();
+    QUIET_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !SOLVING.with(Cell::get) {
+                default_hook(info);
+            }
+        }));
+    });
+
+    let was_solving = SOLVING.with(|solving| solving.replace(true));
+    let outcome = panic::catch_unwind(panic::AssertUnwindSafe(solve));
+    SOLVING.with(|solving| solving.set(was_solving));
+    outcome.map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default()
+    })
+}
+
+struct Registration {
+    format_solutions: FormatSolutionsFn,
+    params: &'static [&'static str],
 }
-type FormatSolutionsFn = fn(input: &str) -> FormattedSolutions;
 
 pub struct Challenges {
-    challenges_by_day: HashMap<u8, FormatSolutionsFn>,
+    challenges_by_day: BTreeMap<u8, Registration>,
 }
 
-fn solve_challenge_and_format_solutions<T: Challenge>(input: &str) -> FormattedSolutions {
-    let challenge = T::new(input);
-    FormattedSolutions {
-        part1: challenge.solve_part1().to_string(),
-        part2: challenge.solve_part2().to_string(),
+/// Solves a part and explains it, returning the formatted answer and the explanation
+type ExplainFn<T> = fn(&T, Part) -> ChallengeResult<(String, String)>;
+
+fn solve_challenge_and_format_solutions<T: Challenge>(
+    input: &str,
+    options: &SolveOptions,
+) -> ChallengeResult<DaySolutions> {
+    solve_and_format::<T>(input, options, None)
+}
+
+fn solve_explain_and_format_solutions<T: Explain>(
+    input: &str,
+    options: &SolveOptions,
+) -> ChallengeResult<DaySolutions> {
+    let explain: ExplainFn<T> = |challenge, part| match part {
+        Part::One => challenge
+            .explain_part1()
+            .map(|(answer, explanation)| (answer.to_string(), explanation)),
+        Part::Two => challenge
+            .explain_part2()
+            .map(|(answer, explanation)| (answer.to_string(), explanation)),
+    };
+    solve_and_format::<T>(input, options, Some(explain))
+}
+
+fn solve_and_format<T: Challenge>(
+    input: &str,
+    options: &SolveOptions,
+    explain: Option<ExplainFn<T>>,
+) -> ChallengeResult<DaySolutions> {
+    let (challenge, parse_timing) = measure(options.repetitions, || {
+        T::with_params(input, &options.params)
+    });
+    let challenge = challenge.map_err(|error| error.in_day(T::DAY))?;
+    let solve_part = |part: Part, solve: &dyn Fn() -> ChallengeResult<String>| {
+        options.includes(part).then(|| {
+            let in_context = |error: ChallengeError| error.in_day(T::DAY).in_part(part);
+            match explain.filter(|_| options.explain) {
+                Some(explain) => {
+                    let (outcome, timing) =
+                        measure(options.repetitions, || explain(&challenge, part));
+                    let (answer, explanation) = match outcome {
+                        Ok((answer, explanation)) => (Ok(answer), Some(Ok(explanation))),
+                        Err(error) => (Err(in_context(error)), None),
+                    };
+                    PartSolution {
+                        answer,
+                        timing,
+                        explanation,
+                    }
+                }
+                None => {
+                    let (answer, timing) = measure(options.repetitions, solve);
+                    PartSolution {
+                        answer: answer.map_err(in_context),
+                        timing,
+                        explanation: None,
+                    }
+                }
+            }
+        })
+    };
+    Ok(DaySolutions {
+        parse_timing,
+        part1: solve_part(Part::One, &|| {
+            challenge.solve_part1().map(|solution| solution.to_string())
+        }),
+        part2: solve_part(Part::Two, &|| {
+            challenge.solve_part2().map(|solution| solution.to_string())
+        }),
+    })
+}
+
+impl Default for Challenges {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl Challenges {
     pub fn new() -> Challenges {
         let mut challenges = Challenges {
-            challenges_by_day: HashMap::new(),
+            challenges_by_day: BTreeMap::new(),
         };
         challenges.register::<day01::Day01>();
         challenges.register::<day02::Day02>();
@@ -80,16 +503,16 @@ impl Challenges {
         challenges.register::<day10::Day10>();
         challenges.register::<day11::Day11>();
         challenges.register::<day12::Day12>();
-        challenges.register::<day13::Day13>();
+        challenges.register_explained::<day13::Day13>();
         challenges.register::<day14::Day14>();
         challenges.register::<day15::Day15>();
         challenges.register::<day16::Day16>();
         challenges.register::<day17::Day17>();
         challenges.register::<day18::Day18>();
-        challenges.register::<day19::Day19>();
+        challenges.register_explained::<day19::Day19>();
         challenges.register::<day20::Day20>();
-        challenges.register::<day21::Day21>();
-        challenges.register::<day22::Day22>();
+        challenges.register_explained::<day21::Day21>();
+        challenges.register_explained::<day22::Day22>();
         challenges.register::<day23::Day23>();
         challenges.register::<day24::Day24>();
         challenges.register::<day25::Day25>();
@@ -97,19 +520,331 @@ impl Challenges {
     }
 
     fn register<T: Challenge>(&mut self) {
+        self.challenges_by_day.insert(
+            T::DAY,
+            Registration {
+                format_solutions: solve_challenge_and_format_solutions::<T>,
+                params: T::PARAMS,
+            },
+        );
+    }
+
+    fn register_explained<T: Explain>(&mut self) {
+        self.challenges_by_day.insert(
+            T::DAY,
+            Registration {
+                format_solutions: solve_explain_and_format_solutions::<T>,
+                params: T::PARAMS,
+            },
+        );
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = u8> + '_ {
+        self.challenges_by_day.keys().copied()
+    }
+
+    /// Names of the puzzle constants `day` can override, empty if the day isn't registered
+    pub fn params(&self, day: u8) -> &'static [&'static str] {
         self.challenges_by_day
-            .insert(T::DAY, solve_challenge_and_format_solutions::<T>);
+            .get(&day)
+            .map_or(&[], |registration| registration.params)
     }
 
-    pub fn print_solutions(&self, day: u8) {
-        let input = fs::read_to_string(format!("./input/day{:02}.txt", day)).unwrap();
-        let solutions = self
+    /// The first name in `params` that none of `days` reads, since every day sees all the params
+    /// and a typo or a param meant for another day would otherwise be ignored silently
+    pub fn unknown_param<'a>(&self, days: &[u8], params: &'a Params) -> Option<&'a str> {
+        params
+            .names()
+            .find(|name| !days.iter().any(|&day| self.params(day).contains(name)))
+    }
+
+    /// Solves `days` on up to `jobs` threads. The outcomes are passed to `report` as soon as they
+    /// are available, but always in the order of `days`
+    pub fn solve_days(
+        &self,
+        days: &[u8],
+        input: &InputSource,
+        options: &SolveOptions,
+        jobs: usize,
+        mut report: impl FnMut(u8, ChallengeResult<DaySolutions>),
+    ) {
+        let next_index = &AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+        thread::scope(|scope| {
+            for _ in 0..jobs.clamp(1, days.len().max(1)) {
+                let sender = sender.clone();
+                thread::Builder::new()
+                    // some solvers recurse deeply, give them as much stack as the main thread
+                    .stack_size(SOLVER_STACK_SIZE)
+                    .spawn_scoped(scope, move || loop {
+                        let index = next_index.fetch_add(1, Ordering::Relaxed);
+                        let Some(&day) = days.get(index) else {
+                            break;
+                        };
+                        let outcome = self.solve(day, input, options);
+                        if sender.send((index, outcome)).is_err() {
+                            break;
+                        }
+                    })
+                    .expect("failed to spawn solver thread");
+            }
+            drop(sender);
+
+            let mut finished_out_of_order = BTreeMap::new();
+            let mut next_to_report = 0;
+            for (index, outcome) in receiver {
+                finished_out_of_order.insert(index, outcome);
+                while let Some(outcome) = finished_out_of_order.remove(&next_to_report) {
+                    report(days[next_to_report], outcome);
+                    next_to_report += 1;
+                }
+            }
+        });
+    }
+
+    /// Reads the input of `day` and solves it
+    pub fn solve(
+        &self,
+        day: u8,
+        input: &InputSource,
+        options: &SolveOptions,
+    ) -> ChallengeResult<DaySolutions> {
+        if !self.challenges_by_day.contains_key(&day) {
+            return Err(ChallengeError::new("no solution registered").in_day(day));
+        }
+        let input = input.read(day).map_err(|error| error.in_day(day))?;
+        self.solve_input(day, &input, options)
+    }
+
+    /// Solves `day` for the given puzzle input, turning a panicking solver into an error
+    pub fn solve_input(
+        &self,
+        day: u8,
+        input: &str,
+        options: &SolveOptions,
+    ) -> ChallengeResult<DaySolutions> {
+        let format_solutions = self
             .challenges_by_day
             .get(&day)
-            .expect(&format!("no solution for day {}", day))(&input);
+            .ok_or_else(|| ChallengeError::new("no solution registered").in_day(day))?
+            .format_solutions;
+        catch_solver_panic(|| format_solutions(input, options)).unwrap_or_else(|message| {
+            Err(ChallengeError::new(format!("panicked: {}", message)).in_day(day))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timing_from_samples() {
+        let timing = Timing::from_samples([5, 1, 3].map(Duration::from_millis).to_vec());
+        assert_eq!(timing.repetitions, 3);
+        assert_eq!(timing.min, Duration::from_millis(1));
+        assert_eq!(timing.median, Duration::from_millis(3));
+        assert_eq!(timing.max, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn test_catch_solver_panic() {
+        assert_eq!(catch_solver_panic(|| 7), Ok(7));
+        assert_eq!(
+            catch_solver_panic(|| -> u8 { panic!("bad input") }),
+            Err("bad input".to_owned())
+        );
+        assert!(!SOLVING.with(Cell::get));
+    }
+
+    #[test]
+    fn test_measure_stops_at_first_error() {
+        let mut runs = 0;
+        let (result, timing) = measure(5, || {
+            runs += 1;
+            if runs < 3 {
+                Ok(runs)
+            } else {
+                Err(ChallengeError::new("failed"))
+            }
+        });
+        assert_eq!(result, Err(ChallengeError::new("failed")));
+        assert_eq!(timing.repetitions, 3);
+
+        let (result, timing) = measure(4, || Ok(()));
+        assert_eq!(result, Ok(()));
+        assert_eq!(timing.repetitions, 4);
+    }
+
+    #[test]
+    fn test_solve_days_reports_in_order() {
+        let challenges = Challenges::new();
+        let days = [2, 1, 99, 5, 3];
+        let mut reported = Vec::new();
+        challenges.solve_days(
+            &days,
+            &InputSource::Directory(PathBuf::from("input")),
+            &SolveOptions::default(),
+            4,
+            |day, outcome| reported.push((day, outcome.is_ok())),
+        );
+        assert_eq!(
+            reported,
+            vec![(2, true), (1, true), (99, false), (5, true), (3, true)]
+        );
+    }
+
+    #[test]
+    fn test_solve_single_part() {
+        let options = SolveOptions {
+            part: Some(Part::Two),
+            ..SolveOptions::default()
+        };
+        let solutions =
+            solve_challenge_and_format_solutions::<day01::Day01>("(()))", &options).unwrap();
+        assert_eq!(solutions.part1, None);
+        assert_eq!(solutions.part2.unwrap().answer, Ok("5".to_owned()));
+    }
+
+    #[test]
+    fn test_solve_with_explanation() {
+        let options = SolveOptions {
+            part: Some(Part::One),
+            explain: true,
+            ..SolveOptions::default()
+        };
+        let input = "Hit Points: 13\nDamage: 8\n";
+        let params: Params = r#"{"hit_points": 10, "mana": 250}"#.parse().unwrap();
+        let solutions = solve_explain_and_format_solutions::<day22::Day22>(
+            input,
+            &SolveOptions { params, ..options },
+        )
+        .unwrap();
+        let part1 = solutions.part1.unwrap();
+        assert_eq!(part1.answer, Ok("226".to_owned()));
+        let explanation = part1.explanation.unwrap().unwrap();
+        assert_eq!(
+            explanation.lines().next(),
+            Some("casting Poison, Magic Missile spends 226 mana")
+        );
+    }
+
+    #[test]
+    fn test_unknown_param() {
+        let challenges = Challenges::new();
+        let mut params = Params::default();
+        params.set("liters", "25");
+        assert_eq!(challenges.unknown_param(&[17], &params), None);
+        assert_eq!(challenges.unknown_param(&[1, 17], &params), None);
+        assert_eq!(challenges.unknown_param(&[18], &params), Some("liters"));
+        params.set("steps", "4");
+        assert_eq!(challenges.unknown_param(&[17, 18], &params), None);
+        assert_eq!(challenges.unknown_param(&[17], &params), Some("steps"));
+    }
+
+    /// An example from `examples/dayNN/*.txt`: `part1: <answer>` and/or `part2: <answer>` header
+    /// lines, a `---` separator and the puzzle input
+    struct Example {
+        expected: Vec<(Part, String)>,
+        params: Params,
+        input: String,
+    }
+
+    impl FromStr for Example {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let s = s.replace("\r\n", "\n");
+            let (header, input) = s
+                .split_once("---\n")
+                .ok_or("missing --- separator between header and input")?;
+            let mut expected = Vec::new();
+            let mut params = Params::default();
+            for line in header.lines() {
+                match line.split_once(':') {
+                    Some(("part1", answer)) => expected.push((Part::One, answer.trim().to_owned())),
+                    Some(("part2", answer)) => expected.push((Part::Two, answer.trim().to_owned())),
+                    Some(("param", param)) => match param.split_once('=') {
+                        Some((name, value)) => params.set(name.trim(), value.trim()),
+                        None => return Err(format!("invalid param: {:?}", param)),
+                    },
+                    _ => return Err(format!("invalid header line: {:?}", line)),
+                }
+            }
+            Ok(Self {
+                expected,
+                params,
+                input: input.to_owned(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_examples() {
+        let challenges = Challenges::new();
+        let mut failures = Vec::new();
+        let mut num_examples = 0;
+        for day in challenges.days() {
+            let Ok(entries) = fs::read_dir(format!("examples/day{:02}", day)) else {
+                continue;
+            };
+            let mut paths: Vec<_> = entries.map(|entry| entry.unwrap().path()).collect();
+            paths.sort();
+            for path in paths
+                .iter()
+                .filter(|path| path.extension() == Some("txt".as_ref()))
+            {
+                num_examples += 1;
+                let example: Example = match fs::read_to_string(path).unwrap().parse() {
+                    Ok(example) => example,
+                    Err(error) => {
+                        failures.push(format!("{}: {}", path.display(), error));
+                        continue;
+                    }
+                };
+                // only solve the parts the example has answers for, the input may not be valid
+                // for the other part
+                let options = SolveOptions {
+                    part: match example.expected.as_slice() {
+                        [(part, _)] => Some(*part),
+                        _ => None,
+                    },
+                    params: example.params.clone(),
+                    ..SolveOptions::default()
+                };
+                let solutions = match challenges.solve_input(day, &example.input, &options) {
+                    Ok(solutions) => solutions,
+                    Err(error) => {
+                        failures.push(format!("{}: {}", path.display(), error));
+                        continue;
+                    }
+                };
+                for (part, expected) in &example.expected {
+                    match solutions.part(*part).map(|solution| &solution.answer) {
+                        Some(Ok(answer)) if answer == expected => {}
+                        Some(Ok(answer)) => failures.push(format!(
+                            "{}, {}: expected {}, got {}",
+                            path.display(),
+                            part,
+                            expected,
+                            answer
+                        )),
+                        Some(Err(error)) => failures.push(format!("{}: {}", path.display(), error)),
+                        None => failures.push(format!("{}, {}: not solved", path.display(), part)),
+                    }
+                }
+            }
+        }
+        assert!(num_examples > 0, "no examples found");
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
 
-        println!("Solutions for day {}:", day);
-        println!("  part 1: {} ", solutions.part1);
-        println!("  part 2: {} ", solutions.part2);
+    #[test]
+    fn test_example_with_crlf_line_endings() {
+        let example: Example = "part1: -1\r\nparam: x = 2\r\n---\r\n()())\r\n"
+            .parse()
+            .unwrap();
+        assert_eq!(example.expected, vec![(Part::One, "-1".to_owned())]);
+        assert_eq!(example.input, "()())\n");
     }
 }
@@ -1,7 +1,7 @@
-use super::Challenge;
+use super::{Challenge, ChallengeError, ChallengeResult};
 
 pub struct Day01 {
-    input: String,
+    floor_changes: Vec<i32>,
 }
 
 impl Challenge for Day01 {
@@ -9,47 +9,37 @@ impl Challenge for Day01 {
     type Part1Solution = i32;
     type Part2Solution = usize;
 
-    fn new(input: &str) -> Self {
-        Self {
-            input: input.trim().to_owned(),
-        }
+    fn new(input: &str) -> ChallengeResult<Self> {
+        let floor_changes = input
+            .trim()
+            .chars()
+            .zip(1..)
+            .map(|(c, column)| match c {
+                '(' => Ok(1),
+                ')' => Ok(-1),
+                _ => Err(ChallengeError::at(
+                    1,
+                    column,
+                    format!("invalid character: {:?}", c),
+                )),
+            })
+            .collect::<ChallengeResult<_>>()?;
+        Ok(Self { floor_changes })
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
-        let mut floor = 0;
-        for c in self.input.chars() {
-            match c {
-                '(' => {
-                    floor += 1;
-                }
-                ')' => {
-                    floor -= 1;
-                }
-                _ => panic!("invalid character"),
-            }
-        }
-        floor
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        Ok(self.floor_changes.iter().sum())
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
         let mut floor = 0;
-        for (c, i) in self.input.chars().zip(1..) {
-            match c {
-                '(' => {
-                    floor += 1;
-                }
-                ')' => {
-                    floor -= 1;
-                }
-                _ => {
-                    panic!("invalid character");
-                }
-            }
+        for (floor_change, i) in self.floor_changes.iter().zip(1..) {
+            floor += floor_change;
             if floor < 0 {
-                return i;
+                return Ok(i);
             }
         }
-        panic!("never entered basement");
+        Err(ChallengeError::new("never entered basement"))
     }
 }
 
@@ -59,14 +49,23 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(Day01::new("(())").solve_part1(), 0);
-        assert_eq!(Day01::new("()()").solve_part1(), 0);
-        assert_eq!(Day01::new("(()(()(").solve_part1(), 3);
+        assert_eq!(Day01::new("(())").unwrap().solve_part1(), Ok(0));
+        assert_eq!(Day01::new("()()").unwrap().solve_part1(), Ok(0));
+        assert_eq!(Day01::new("(()(()(").unwrap().solve_part1(), Ok(3));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(Day01::new(")").solve_part2(), 1);
-        assert_eq!(Day01::new("()())").solve_part2(), 5);
+        assert_eq!(Day01::new(")").unwrap().solve_part2(), Ok(1));
+        assert_eq!(Day01::new("()())").unwrap().solve_part2(), Ok(5));
+        assert!(Day01::new("((").unwrap().solve_part2().is_err());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert_eq!(
+            Day01::new("(()x").err(),
+            Some(ChallengeError::at(1, 4, "invalid character: 'x'"))
+        );
     }
 }
@@ -1,4 +1,4 @@
-use super::Challenge;
+use super::{parse_lines, Challenge, ChallengeResult};
 use std::error::Error;
 use std::str::FromStr;
 
@@ -11,26 +11,26 @@ impl Challenge for Day02 {
     type Part1Solution = u32;
     type Part2Solution = u32;
 
-    fn new(input: &str) -> Self {
-        let list_of_dimensions: Vec<_> = input
-            .lines()
-            .map(|dimensions_str| dimensions_str.parse::<Dimensions>().unwrap())
-            .collect();
-        Self { list_of_dimensions }
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Ok(Self {
+            list_of_dimensions: parse_lines(input)?,
+        })
     }
 
-    fn solve_part1(&self) -> u32 {
-        self.list_of_dimensions
+    fn solve_part1(&self) -> ChallengeResult<u32> {
+        Ok(self
+            .list_of_dimensions
             .iter()
             .map(|dimensions| dimensions.required_wrapping_paper())
-            .sum()
+            .sum())
     }
 
-    fn solve_part2(&self) -> u32 {
-        self.list_of_dimensions
+    fn solve_part2(&self) -> ChallengeResult<u32> {
+        Ok(self
+            .list_of_dimensions
             .iter()
             .map(|dimensions| dimensions.required_ribbon())
-            .sum()
+            .sum())
     }
 }
 
@@ -83,7 +83,7 @@ impl Dimensions {
     }
 
     fn volume(&self) -> u32 {
-        return self.l * self.w * self.h;
+        self.l * self.w * self.h
     }
 }
 
@@ -1,4 +1,4 @@
-use super::Challenge;
+use super::{Challenge, ChallengeError, ChallengeResult};
 use std::collections::HashSet;
 
 pub struct Day03 {
@@ -47,27 +47,30 @@ impl Challenge for Day03 {
     type Part1Solution = usize;
     type Part2Solution = usize;
 
-    fn new(input: &str) -> Self {
-        let list_of_directions: Vec<_> = input
+    fn new(input: &str) -> ChallengeResult<Self> {
+        let list_of_directions = input
             .trim()
             .chars()
-            .map(|c| match c {
-                '^' => Direction::North,
-                '>' => Direction::East,
-                'v' => Direction::South,
-                '<' => Direction::West,
-                _ => {
-                    panic!("invalid character: {}", c);
-                }
+            .zip(1..)
+            .map(|(c, column)| match c {
+                '^' => Ok(Direction::North),
+                '>' => Ok(Direction::East),
+                'v' => Ok(Direction::South),
+                '<' => Ok(Direction::West),
+                _ => Err(ChallengeError::at(
+                    1,
+                    column,
+                    format!("invalid character: {:?}", c),
+                )),
             })
-            .collect();
-        Self { list_of_directions }
+            .collect::<ChallengeResult<_>>()?;
+        Ok(Self { list_of_directions })
     }
-    fn solve_part1(&self) -> Self::Part1Solution {
-        self.count_visited_by_santa()
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        Ok(self.count_visited_by_santa())
     }
-    fn solve_part2(&self) -> Self::Part2Solution {
-        self.count_visited_by_santa_or_helper()
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        Ok(self.count_visited_by_santa_or_helper())
     }
 }
 
@@ -90,17 +93,30 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(Day03::new(">").count_visited_by_santa(), 2);
-        assert_eq!(Day03::new("^>v<").count_visited_by_santa(), 4);
-        assert_eq!(Day03::new("^v^v^v^v^v").count_visited_by_santa(), 2);
+        assert_eq!(Day03::new(">").unwrap().count_visited_by_santa(), 2);
+        assert_eq!(Day03::new("^>v<").unwrap().count_visited_by_santa(), 4);
+        assert_eq!(
+            Day03::new("^v^v^v^v^v").unwrap().count_visited_by_santa(),
+            2
+        );
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(Day03::new("^v").count_visited_by_santa_or_helper(), 3);
-        assert_eq!(Day03::new("^>v<").count_visited_by_santa_or_helper(), 3);
         assert_eq!(
-            Day03::new("^v^v^v^v^v").count_visited_by_santa_or_helper(),
+            Day03::new("^v").unwrap().count_visited_by_santa_or_helper(),
+            3
+        );
+        assert_eq!(
+            Day03::new("^>v<")
+                .unwrap()
+                .count_visited_by_santa_or_helper(),
+            3
+        );
+        assert_eq!(
+            Day03::new("^v^v^v^v^v")
+                .unwrap()
+                .count_visited_by_santa_or_helper(),
             11
         );
     }
@@ -1,5 +1,4 @@
-use super::Challenge;
-use md5;
+use super::{Challenge, ChallengeError, ChallengeResult};
 
 pub struct Day04 {
     miner: Miner,
@@ -10,15 +9,15 @@ impl Challenge for Day04 {
     type Part1Solution = i32;
     type Part2Solution = i32;
 
-    fn new(input: &str) -> Self {
-        Self {
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Ok(Self {
             miner: Miner::new(input),
-        }
+        })
     }
-    fn solve_part1(&self) -> Self::Part1Solution {
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
         self.miner.mine(5)
     }
-    fn solve_part2(&self) -> Self::Part2Solution {
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
         self.miner.mine(6)
     }
 }
@@ -34,13 +33,10 @@ impl Miner {
         }
     }
 
-    fn mine(&self, num_leading_zeros: usize) -> i32 {
-        for i in 1.. {
-            if self.answer_yields_digest_with_num_leading_zeros(i, num_leading_zeros) {
-                return i;
-            }
-        }
-        panic!("loop ran 0 times");
+    fn mine(&self, num_leading_zeros: usize) -> ChallengeResult<i32> {
+        (1..=i32::MAX)
+            .find(|&i| self.answer_yields_digest_with_num_leading_zeros(i, num_leading_zeros))
+            .ok_or_else(|| ChallengeError::new("no answer up to i32::MAX yields such a digest"))
     }
 
     fn answer_yields_digest_with_num_leading_zeros(
@@ -79,6 +75,6 @@ mod tests {
     #[ignore]
     #[test]
     fn mine() {
-        assert_eq!(Miner::new("pqrstuv").mine(5), 1048970)
+        assert_eq!(Miner::new("pqrstuv").mine(5), Ok(1048970))
     }
 }
@@ -1,11 +1,11 @@
-use super::Challenge;
+use super::{Challenge, ChallengeResult};
 
 pub struct Day05 {
     lines: Vec<String>,
 }
 
 impl Day05 {
-    fn count_lines_satisfying(&self, predicate: fn (&str) -> bool) -> usize {
+    fn count_lines_satisfying(&self, predicate: fn(&str) -> bool) -> usize {
         self.lines
             .iter()
             .map(|line| predicate(line))
@@ -19,15 +19,15 @@ impl Challenge for Day05 {
     type Part1Solution = usize;
     type Part2Solution = usize;
 
-    fn new(input: &str) -> Self {
+    fn new(input: &str) -> ChallengeResult<Self> {
         let lines: Vec<String> = input.lines().map(|line| line.to_owned()).collect();
-        Self { lines }
+        Ok(Self { lines })
     }
-    fn solve_part1(&self) -> Self::Part1Solution {
-        self.count_lines_satisfying(is_nice_part1)
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        Ok(self.count_lines_satisfying(is_nice_part1))
     }
-    fn solve_part2(&self) -> Self::Part2Solution {
-        self.count_lines_satisfying(is_nice_part2)
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        Ok(self.count_lines_satisfying(is_nice_part2))
     }
 }
 
@@ -1,8 +1,8 @@
 use std::cmp::{max, min};
-use std::error::Error;
 use std::str::FromStr;
 
-use super::Challenge;
+use super::parsing::{ParseError, Parser};
+use super::{parse_lines, Challenge, ChallengeError, ChallengeResult};
 
 pub struct Day06 {
     instructions: Vec<Instruction>,
@@ -13,24 +13,31 @@ impl Challenge for Day06 {
     type Part1Solution = usize;
     type Part2Solution = u32;
 
-    fn new(input: &str) -> Self {
-        Self {
-            instructions: input.lines().map(|line| line.parse().unwrap()).collect(),
+    fn new(input: &str) -> ChallengeResult<Self> {
+        let instructions: Vec<Instruction> = parse_lines(input)?;
+        for (instruction, line_number) in instructions.iter().zip(1..) {
+            if !instruction.is_within_grid() {
+                return Err(ChallengeError::at_line(
+                    line_number,
+                    "coordinates outside of the light grid",
+                ));
+            }
         }
+        Ok(Self { instructions })
     }
-    fn solve_part1(&self) -> Self::Part1Solution {
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
         let mut lights = Lights::new();
         for instruction in &self.instructions {
             lights.execute_instruction(*instruction);
         }
-        lights.count_on()
+        Ok(lights.count_on())
     }
-    fn solve_part2(&self) -> Self::Part2Solution {
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
         let mut lights = DimmableLights::new();
         for instruction in &self.instructions {
             lights.execute_instruction(*instruction);
         }
-        lights.total_brightness()
+        Ok(lights.total_brightness())
     }
 }
 
@@ -41,6 +48,14 @@ struct Instruction {
     coordinate2: Coordinate,
 }
 
+impl Instruction {
+    fn is_within_grid(&self) -> bool {
+        [self.coordinate1, self.coordinate2]
+            .iter()
+            .all(|coordinate| coordinate.x < NUM_LIGHTS_X && coordinate.y < NUM_LIGHTS_Y)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Action {
     Toggle,
@@ -77,19 +92,19 @@ fn coordinates_in_range(
     let min_y = min(coordinate1.y, coordinate2.y);
     let max_y = max(coordinate1.y, coordinate2.y);
     (min_x..=max_x)
-        .into_iter()
         .flat_map(move |x| std::iter::repeat(x).zip(min_y..=max_y))
         .map(|(x, y)| Coordinate { x, y })
 }
 
 struct Lights {
-    grid: [[LightState; NUM_LIGHTS_Y]; NUM_LIGHTS_X],
+    // on the heap, the grid is too large for the stack of a solver thread
+    grid: Vec<[LightState; NUM_LIGHTS_Y]>,
 }
 
 impl Lights {
     fn new() -> Self {
         Lights {
-            grid: [[LightState::Off; NUM_LIGHTS_Y]; NUM_LIGHTS_X],
+            grid: vec![[LightState::Off; NUM_LIGHTS_Y]; NUM_LIGHTS_X],
         }
     }
 
@@ -136,13 +151,13 @@ impl Lights {
 }
 
 struct DimmableLights {
-    grid: [[u32; NUM_LIGHTS_Y]; NUM_LIGHTS_X],
+    grid: Vec<[u32; NUM_LIGHTS_Y]>,
 }
 
 impl DimmableLights {
     fn new() -> Self {
         Self {
-            grid: [[0; NUM_LIGHTS_Y]; NUM_LIGHTS_X],
+            grid: vec![[0; NUM_LIGHTS_Y]; NUM_LIGHTS_X],
         }
     }
 
@@ -170,12 +185,12 @@ impl DimmableLights {
     }
 
     fn execute_instruction(&mut self, instruction: Instruction) {
-        let (method, amount): (fn(&mut DimmableLights, u32, Coordinate, Coordinate), u32) =
-            match instruction.action {
-                Action::TurnOn => (Self::increase_brightness, 1),
-                Action::TurnOff => (Self::decrease_brightness, 1),
-                Action::Toggle => (Self::increase_brightness, 2),
-            };
+        type BrightnessMethod = fn(&mut DimmableLights, u32, Coordinate, Coordinate);
+        let (method, amount): (BrightnessMethod, u32) = match instruction.action {
+            Action::TurnOn => (Self::increase_brightness, 1),
+            Action::TurnOff => (Self::decrease_brightness, 1),
+            Action::Toggle => (Self::increase_brightness, 2),
+        };
         method(
             self,
             amount,
@@ -186,32 +201,29 @@ impl DimmableLights {
 
     fn total_brightness(&self) -> u32 {
         coordinates_in_range(BOTTOM_LEFT, TOP_RIGHT)
-            .into_iter()
             .map(|coordinate| self.grid[coordinate.x][coordinate.y])
             .sum()
     }
 }
 
 struct InstructionParser<'a> {
-    input: &'a str,
-    cursor: usize,
+    parser: Parser<'a>,
 }
 
-type ParseError = Box<dyn Error>;
-
 impl<'a> InstructionParser<'a> {
-    fn new(input: &str) -> InstructionParser {
-        InstructionParser { input, cursor: 0 }
+    fn new(input: &str) -> InstructionParser<'_> {
+        InstructionParser {
+            parser: Parser::new(input),
+        }
     }
 
     fn parse(&mut self) -> Result<Instruction, ParseError> {
         let action = self.parse_action()?;
-        self.parse_space()?;
+        self.parser.expect_literal(" ")?;
         let coordinate1 = self.parse_coordinate()?;
-        self.parse_space()?;
-        self.parse_literal("through")?;
-        self.parse_space()?;
+        self.parser.expect_literal(" through ")?;
         let coordinate2 = self.parse_coordinate()?;
+        self.parser.expect_end()?;
         Ok(Instruction {
             action,
             coordinate1,
@@ -219,73 +231,29 @@ impl<'a> InstructionParser<'a> {
         })
     }
 
-    fn remaining_input(&self) -> &str {
-        &self.input[self.cursor..]
-    }
-
-    fn parse_literal(&mut self, literal: &str) -> Result<(), ParseError> {
-        if self.remaining_input().starts_with(literal) {
-            self.cursor += literal.len();
-            Ok(())
-        } else {
-            Err(format!("expected literal: {}", literal).into())
-        }
-    }
-
-    fn parse_number(&mut self) -> Result<u32, ParseError> {
-        let mut len = self.remaining_input().len();
-        for (i, c) in self.remaining_input().char_indices() {
-            if !c.is_numeric() {
-                len = i;
-                break;
-            }
-        }
-
-        // println!(
-        //     "parsing: {}, remaining_input: {}",
-        //     &self.remaining_input()[..len],
-        //     &self.remaining_input()
-        // );
-        let number = self.remaining_input()[..len].parse()?;
-        self.cursor += len;
-        Ok(number)
-    }
-
-    fn parse_space(&mut self) -> Result<(), ParseError> {
-        self.parse_literal(" ")
-    }
-
-    fn parse_comma(&mut self) -> Result<(), ParseError> {
-        self.parse_literal(",")
-    }
-
     fn parse_action(&mut self) -> Result<Action, ParseError> {
-        if self.parse_literal("turn on").is_ok() {
+        if self.parser.try_literal("turn on") {
             Ok(Action::TurnOn)
-        } else if self.parse_literal("turn off").is_ok() {
+        } else if self.parser.try_literal("turn off") {
             Ok(Action::TurnOff)
-        } else if self.parse_literal("toggle").is_ok() {
+        } else if self.parser.try_literal("toggle") {
             Ok(Action::Toggle)
         } else {
-            Err("expected action".into())
+            Err(self.parser.error("expected action"))
         }
     }
 
     fn parse_coordinate(&mut self) -> Result<Coordinate, ParseError> {
-        let x = self.parse_number()?;
-        self.parse_comma()?;
-        let y = self.parse_number()?;
-        Ok(Coordinate {
-            x: x as usize,
-            y: y as usize,
-        })
+        let x = self.parser.parse_number()?;
+        self.parser.expect_literal(",")?;
+        let y = self.parser.parse_number()?;
+        Ok(Coordinate { x, y })
     }
 }
 
 impl FromStr for Instruction {
-    type Err = Box<dyn Error>;
+    type Err = ParseError;
 
-    // Required method
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         InstructionParser::new(s).parse()
     }
@@ -337,16 +305,18 @@ mod tests {
     }
 
     #[test]
-    fn parse_number() {
-        assert_eq!(InstructionParser::new("456").parse_number().unwrap(), 456);
+    fn parse_invalid_instruction() {
         assert_eq!(
-            InstructionParser::new("12 and more")
-                .parse_number()
-                .unwrap(),
-            12
+            "toggle 0,1 thru 123,456"
+                .parse::<Instruction>()
+                .unwrap_err()
+                .to_string(),
+            "expected \" through \" at offset 10, found \" thru 123,456\""
         );
-        assert!(InstructionParser::new("").parse_number().is_err());
-        assert!(InstructionParser::new(" abc ").parse_number().is_err());
+        assert!("turn on 0,1 through 2,3 and more"
+            .parse::<Instruction>()
+            .is_err());
+        assert!("flip 0,1 through 2,3".parse::<Instruction>().is_err());
     }
 
     #[test]
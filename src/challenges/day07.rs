@@ -1,9 +1,29 @@
-use std::{collections::HashMap, str::FromStr};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{self, Display};
+use std::str::FromStr;
 
-use super::Challenge;
+use super::{parse_lines, Challenge, ChallengeError, ChallengeResult};
+use crate::params::{day_params, Params};
 
 pub struct Day07 {
     instructions: Vec<Instruction>,
+    params: Day07Params,
+}
+
+day_params! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Day07Params {
+        /// The wire whose signal is the answer
+        wire: String = "a".to_owned(),
+        /// The wire that gets the part 1 answer as its signal in part 2
+        override_wire: String = "b".to_owned(),
+    }
+}
+
+impl Day07 {
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
 }
 
 impl Challenge for Day07 {
@@ -11,57 +31,61 @@ impl Challenge for Day07 {
     type Part1Solution = u16;
     type Part2Solution = u16;
 
-    fn new(input: &str) -> Self {
-        let mut instructions: Vec<_> = input
-            .lines()
-            .map(|line| line.parse().expect(&format!("invalid line: {}", line)))
-            .collect();
-        instructions.sort_topologically();
-        Self { instructions }
+    const PARAMS: &'static [&'static str] = Day07Params::NAMES;
+
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Self::with_params(input, &Params::default())
     }
-    fn solve_part1(&self) -> Self::Part1Solution {
-        let wire_values = Emulator::new()
-            .execute_instructions(&self.instructions)
-            .unwrap();
-        *wire_values.get("a").unwrap()
+
+    fn with_params(input: &str, params: &Params) -> ChallengeResult<Self> {
+        Ok(Self {
+            instructions: parse_lines(input)?,
+            params: Day07Params::from_params(params)?,
+        })
     }
-    fn solve_part2(&self) -> Self::Part2Solution {
-        let original_a_value = self.solve_part1();
-        let b_override: Instruction = format!("{} -> b", original_a_value).parse().unwrap();
 
-        let new_instructions: Vec<_> = self
-            .instructions
-            .iter()
-            .map(|instruction| {
-                if instruction.output == "b" {
-                    &b_override
-                } else {
-                    instruction
-                }
-            })
-            .collect();
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        Evaluator::new(&self.instructions)
+            .and_then(|mut evaluator| evaluator.wire_value(&self.params.wire))
+            .map_err(ChallengeError::new)
+    }
 
-        let new_wire_values = Emulator::new().execute_instructions(new_instructions).unwrap();
-        *new_wire_values.get("a").unwrap()
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        let mut evaluator = Evaluator::new(&self.instructions).map_err(ChallengeError::new)?;
+        let original_value = evaluator
+            .wire_value(&self.params.wire)
+            .map_err(ChallengeError::new)?;
+        evaluator
+            .apply_overrides([(self.params.override_wire.as_str(), original_value)])
+            .map_err(ChallengeError::new)?;
+        evaluator
+            .wire_value(&self.params.wire)
+            .map_err(ChallengeError::new)
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct Instruction {
-    expression: Expression,
-    output: String,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub expression: Expression,
+    pub output: String,
 }
 
 impl Instruction {
-    fn incoming_wires(&self) -> Dependencies {
+    fn incoming_wires(&self) -> Dependencies<'_> {
         Dependencies {
             operands: self.expression.operands(),
         }
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-enum Expression {
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} -> {}", self.expression, self.output)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expression {
     Assignment(Operand),
     Not(Operand),
     And { lhs: Operand, rhs: Operand },
@@ -71,7 +95,47 @@ enum Expression {
 }
 
 impl Expression {
-    fn operands(&self) -> Operands {
+    fn operands_mut(&mut self) -> impl Iterator<Item = &mut Operand> {
+        match self {
+            Expression::Assignment(operand) | Expression::Not(operand) => [Some(operand), None],
+            Expression::And { lhs, rhs }
+            | Expression::Or { lhs, rhs }
+            | Expression::LShift { lhs, rhs }
+            | Expression::RShift { lhs, rhs } => [Some(lhs), Some(rhs)],
+        }
+        .into_iter()
+        .flatten()
+    }
+
+    /// The value of the expression if all its operands are constants
+    fn constant_value(&self) -> Option<u16> {
+        self.evaluate(|_| Err(None::<ShiftOutOfRange>)).ok()
+    }
+
+    /// Computes the expression, getting the signals of wire operands from `wire_value`
+    fn evaluate<E: From<ShiftOutOfRange>>(
+        &self,
+        wire_value: impl Fn(&str) -> Result<u16, E>,
+    ) -> Result<u16, E> {
+        let operand_value = |operand: &Operand| match operand {
+            Operand::Constant(value) => Ok(*value),
+            Operand::Wire(wire) => wire_value(wire),
+        };
+        let shift = |lhs: &Operand, rhs: &Operand, shift: fn(u16, u32) -> Option<u16>| {
+            let amount = operand_value(rhs)?;
+            shift(operand_value(lhs)?, amount.into()).ok_or(E::from(ShiftOutOfRange(amount)))
+        };
+        Ok(match self {
+            Expression::Assignment(operand) => operand_value(operand)?,
+            Expression::Not(operand) => !operand_value(operand)?,
+            Expression::And { lhs, rhs } => operand_value(lhs)? & operand_value(rhs)?,
+            Expression::Or { lhs, rhs } => operand_value(lhs)? | operand_value(rhs)?,
+            Expression::LShift { lhs, rhs } => shift(lhs, rhs, u16::checked_shl)?,
+            Expression::RShift { lhs, rhs } => shift(lhs, rhs, u16::checked_shr)?,
+        })
+    }
+
+    fn operands(&self) -> Operands<'_> {
         match self {
             Expression::Assignment(operand) | Expression::Not(operand) => Operands {
                 first_operand: operand,
@@ -90,12 +154,50 @@ impl Expression {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-enum Operand {
+impl Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expression::Assignment(operand) => write!(f, "{}", operand),
+            Expression::Not(operand) => write!(f, "NOT {}", operand),
+            Expression::And { lhs, rhs } => write!(f, "{} AND {}", lhs, rhs),
+            Expression::Or { lhs, rhs } => write!(f, "{} OR {}", lhs, rhs),
+            Expression::LShift { lhs, rhs } => write!(f, "{} LSHIFT {}", lhs, rhs),
+            Expression::RShift { lhs, rhs } => write!(f, "{} RSHIFT {}", lhs, rhs),
+        }
+    }
+}
+
+/// A shift by this many bits, which would shift out the whole 16-bit signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShiftOutOfRange(pub u16);
+
+impl Display for ShiftOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "can't shift by {} bits, only by 0 to 15", self.0)
+    }
+}
+
+impl From<ShiftOutOfRange> for ComputeError {
+    fn from(error: ShiftOutOfRange) -> Self {
+        error.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operand {
     Constant(u16),
     Wire(String),
 }
 
+impl Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Constant(value) => write!(f, "{}", value),
+            Operand::Wire(wire) => write!(f, "{}", wire),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Operands<'a> {
     first_operand: &'a Operand,
@@ -133,7 +235,7 @@ impl<'a> Iterator for Dependencies<'a> {
         loop {
             let operand = self.operands.next()?;
             if let Operand::Wire(wire) = operand {
-                return Some(&wire);
+                return Some(wire);
             }
         }
     }
@@ -236,12 +338,18 @@ impl InstructionParser {
             "no more tokens wile parsing binary expression operator",
         ))?;
         let rhs = self.parse_operand()?;
+        if let (Token::LShift | Token::RShift, Operand::Constant(amount)) = (&operator_token, &rhs)
+        {
+            if *amount >= 16 {
+                return Err(ShiftOutOfRange(*amount).to_string());
+            }
+        }
         match operator_token {
             Token::And => Ok(Expression::And { lhs, rhs }),
             Token::Or => Ok(Expression::Or { lhs, rhs }),
             Token::RShift => Ok(Expression::RShift { lhs, rhs }),
             Token::LShift => Ok(Expression::LShift { lhs, rhs }),
-            token @ _ => Err(format!(
+            token => Err(format!(
                 "unexpected token while parsing binary expression operator: {:?}",
                 token
             )),
@@ -299,7 +407,7 @@ impl FromStr for Instruction {
     }
 }
 
-trait TopologicalSort {
+pub trait TopologicalSort {
     fn sort_topologically(&mut self);
 }
 
@@ -325,7 +433,14 @@ impl<'a> TopologicalSorter<'a> {
     fn sort(&mut self) {
         self.find_sorted_wire_indices();
         self.instructions
-            .sort_by_key(|instruction| self.sorted_wire_indices.get(&instruction.output).unwrap())
+            // instructions that can't be sorted (due to cycles or undefined wires) go last, the
+            // emulator will report them as errors
+            .sort_by_key(|instruction| {
+                self.sorted_wire_indices
+                    .get(&instruction.output)
+                    .copied()
+                    .unwrap_or(usize::MAX)
+            })
     }
     fn find_sorted_wire_indices(&mut self) {
         let mut independent_indices: Vec<usize> = Vec::new();
@@ -375,20 +490,23 @@ impl<'a> TopologicalSorter<'a> {
     }
 }
 
-type WireValues = HashMap<String, u16>;
-type ComputeError = String;
-struct Emulator {
+pub type WireValues = HashMap<String, u16>;
+pub type ComputeError = String;
+
+/// Executes instructions in order, so they have to be sorted topologically
+#[derive(Debug, Default)]
+pub struct Emulator {
     wire_values: WireValues,
 }
 
 impl Emulator {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             wire_values: WireValues::new(),
         }
     }
 
-    fn execute_instructions<'a>(
+    pub fn execute_instructions<'a>(
         &mut self,
         instructions: impl IntoIterator<Item = &'a Instruction>,
     ) -> Result<WireValues, ComputeError> {
@@ -400,41 +518,335 @@ impl Emulator {
     }
 
     fn execute_instruction(&mut self, instruction: &Instruction) -> Result<u16, ComputeError> {
-        let output_value = match &instruction.expression {
-            Expression::Assignment(operand) => self.get_operand_value(&operand)?,
-            Expression::Not(operand) => !self.get_operand_value(&operand)?,
-            Expression::And { lhs, rhs } => {
-                self.get_operand_value(&lhs)? & self.get_operand_value(&rhs)?
+        let output_value = instruction.expression.evaluate(|wire| {
+            self.wire_values.get(wire).copied().ok_or(format!(
+                "tried to use value of {} before it was computed",
+                wire
+            ))
+        })?;
+
+        self.wire_values
+            .insert(instruction.output.clone(), output_value);
+        Ok(output_value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvaluationError {
+    /// No instruction drives this wire
+    UndefinedWire(String),
+    /// More than one instruction drives this wire
+    ConflictingDrivers(String),
+    /// The wires of a feedback loop, starting and ending with the same wire
+    Cycle(Vec<String>),
+    /// A wire's signal is used as a shift amount of 16 or more
+    ShiftOutOfRange(ShiftOutOfRange),
+}
+
+impl From<ShiftOutOfRange> for EvaluationError {
+    fn from(error: ShiftOutOfRange) -> Self {
+        Self::ShiftOutOfRange(error)
+    }
+}
+
+impl Display for EvaluationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UndefinedWire(wire) => {
+                write!(f, "wire {} is not driven by any instruction", wire)
+            }
+            Self::ConflictingDrivers(wire) => {
+                write!(f, "wire {} is driven by more than one instruction", wire)
+            }
+            Self::Cycle(wires) => write!(f, "cycle: {}", wires.join(" -> ")),
+            Self::ShiftOutOfRange(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for EvaluationError {}
+
+/// A signal that changed when overriding wires with [`Evaluator::apply_overrides`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WireChange {
+    pub wire: String,
+    pub old_value: u16,
+    pub new_value: u16,
+}
+
+/// Computes the signals of requested wires by resolving their dependencies on demand, remembering
+/// every signal it computes along the way. Unlike [`Emulator`], the instructions don't have to be
+/// sorted, and wires that don't feed into a requested one are never computed
+#[derive(Debug)]
+pub struct Evaluator<'a> {
+    instructions_by_wire: HashMap<&'a str, &'a Instruction>,
+    /// The wires driven by gates that have the key wire as an operand
+    dependents: HashMap<&'a str, Vec<&'a str>>,
+    overrides: WireValues,
+    wire_values: WireValues,
+}
+
+impl<'a> Evaluator<'a> {
+    pub fn new(instructions: &'a [Instruction]) -> Result<Self, EvaluationError> {
+        let mut instructions_by_wire = HashMap::new();
+        let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+        for instruction in instructions {
+            if instructions_by_wire
+                .insert(instruction.output.as_str(), instruction)
+                .is_some()
+            {
+                return Err(EvaluationError::ConflictingDrivers(
+                    instruction.output.clone(),
+                ));
             }
-            Expression::Or { lhs, rhs } => {
-                self.get_operand_value(&lhs)? | self.get_operand_value(&rhs)?
+            for dependency in instruction.incoming_wires() {
+                dependents
+                    .entry(dependency)
+                    .or_default()
+                    .push(&instruction.output);
             }
-            Expression::LShift { lhs, rhs } => {
-                self.get_operand_value(&lhs)? << self.get_operand_value(&rhs)?
+        }
+        Ok(Self {
+            instructions_by_wire,
+            dependents,
+            overrides: WireValues::new(),
+            wire_values: WireValues::new(),
+        })
+    }
+
+    /// Fixes the signal of `wire`, ignoring the instruction that drives it
+    pub fn set_override(&mut self, wire: &str, value: u16) -> Result<(), EvaluationError> {
+        self.apply_overrides([(wire, value)]).map(|_| ())
+    }
+
+    /// Fixes the signals of the given wires, ignoring the instructions that drive them, and
+    /// recomputes the signals computed so far that depend on them. Signals that don't are kept, so
+    /// only the downstream cone of the overridden wires is evaluated again. Returns the previously
+    /// computed signals that changed, sorted by wire. Fails if a recomputed signal can't be
+    /// evaluated with the new values, e.g. when an overridden wire is used as a shift amount of 16
+    /// or more
+    pub fn apply_overrides<'w>(
+        &mut self,
+        overrides: impl IntoIterator<Item = (&'w str, u16)>,
+    ) -> Result<Vec<WireChange>, EvaluationError> {
+        let mut unvisited = Vec::new();
+        for (wire, value) in overrides {
+            self.overrides.insert(wire.to_owned(), value);
+            unvisited.push(wire.to_owned());
+        }
+
+        // a wire that hasn't been computed has no computed dependents either, as computing them
+        // would have computed it
+        let mut old_values = BTreeMap::new();
+        while let Some(wire) = unvisited.pop() {
+            let Some(old_value) = self.wire_values.remove(&wire) else {
+                continue;
+            };
+            if let Some(dependents) = self.dependents.get(wire.as_str()) {
+                unvisited.extend(dependents.iter().map(|&dependent| dependent.to_owned()));
             }
-            Expression::RShift { lhs, rhs } => {
-                self.get_operand_value(&lhs)? >> self.get_operand_value(&rhs)?
+            old_values.insert(wire, old_value);
+        }
+        self.wire_values.extend(self.overrides.clone());
+
+        let mut changes = Vec::new();
+        for (wire, old_value) in old_values {
+            let new_value = self.wire_value(&wire)?;
+            if new_value != old_value {
+                changes.push(WireChange {
+                    wire,
+                    old_value,
+                    new_value,
+                });
             }
-        };
+        }
+        Ok(changes)
+    }
 
+    pub fn wire_value(&mut self, wire: &str) -> Result<u16, EvaluationError> {
+        if let Some(&value) = self.wire_values.get(wire) {
+            return Ok(value);
+        }
+
+        // depth-first through the dependencies, on an explicit stack since circuits can be deep
+        let mut path = vec![self.instruction(wire)?];
+        let mut on_path = HashSet::from([wire]);
+        while let Some(&instruction) = path.last() {
+            let unresolved_dependency = instruction
+                .incoming_wires()
+                .find(|dependency| !self.wire_values.contains_key(*dependency));
+            match unresolved_dependency {
+                Some(dependency) if on_path.contains(dependency) => {
+                    let start = path
+                        .iter()
+                        .position(|instruction| instruction.output == dependency)
+                        .unwrap();
+                    let mut cycle: Vec<_> = path[start..]
+                        .iter()
+                        .map(|instruction| instruction.output.clone())
+                        .collect();
+                    cycle.push(dependency.to_owned());
+                    return Err(EvaluationError::Cycle(cycle));
+                }
+                Some(dependency) => {
+                    path.push(self.instruction(dependency)?);
+                    on_path.insert(dependency);
+                }
+                None => {
+                    let value = instruction
+                        .expression
+                        .evaluate(|wire| Ok::<_, EvaluationError>(self.wire_values[wire]))?;
+                    self.wire_values.insert(instruction.output.clone(), value);
+                    path.pop();
+                    on_path.remove(instruction.output.as_str());
+                }
+            }
+        }
+        Ok(self.wire_values[wire])
+    }
+
+    /// The signals of all the given wires
+    pub fn wire_values<'w>(
+        &mut self,
+        wires: impl IntoIterator<Item = &'w str>,
+    ) -> Result<WireValues, EvaluationError> {
+        wires
+            .into_iter()
+            .map(|wire| Ok((wire.to_owned(), self.wire_value(wire)?)))
+            .collect()
+    }
+
+    /// Every signal computed so far, including overrides
+    pub fn into_wire_values(self) -> WireValues {
         self.wire_values
-            .insert(instruction.output.clone(), output_value);
-        Ok(output_value)
     }
 
-    fn get_operand_value(&self, operand: &Operand) -> Result<u16, ComputeError> {
-        match operand {
-            Operand::Constant(value) => Ok(*value),
-            Operand::Wire(wire) => self
-                .wire_values
-                .get(wire)
-                .map(|value| *value)
-                .ok_or(format!(
-                    "tried to use value of {} before it was computed",
-                    wire
-                )),
+    fn instruction(&self, wire: &str) -> Result<&'a Instruction, EvaluationError> {
+        self.instructions_by_wire
+            .get(wire)
+            .copied()
+            .ok_or_else(|| EvaluationError::UndefinedWire(wire.to_owned()))
+    }
+}
+
+/// Shrinks the circuit to what is needed to compute the `outputs`: gates whose operands are all
+/// known are folded into constants, which are then propagated into the gates that use them, chains
+/// of plain assignments are collapsed, and wires that don't feed into an output are dropped. Wires
+/// in feedback loops are left as they are. The result is sorted topologically, so [`Emulator`]
+/// computes the same signals for the outputs as for the original circuit
+pub fn minimize(instructions: &[Instruction], outputs: &[&str]) -> Vec<Instruction> {
+    let mut sorted = instructions.to_vec();
+    sorted.sort_topologically();
+
+    // what to use instead of each dropped wire: its constant signal, or the wire it's a copy of
+    let mut replacements: HashMap<String, Operand> = HashMap::new();
+    // wires whose signal doesn't depend on a loop or an undefined wire
+    let mut resolvable = HashSet::new();
+    let mut minimized = Vec::new();
+    for mut instruction in sorted {
+        let is_in_loop = instruction
+            .incoming_wires()
+            .any(|dependency| !resolvable.contains(dependency));
+        for operand in instruction.expression.operands_mut() {
+            if let Operand::Wire(wire) = operand {
+                if let Some(replacement) = replacements.get(wire) {
+                    *operand = replacement.clone();
+                }
+            }
+        }
+        if is_in_loop {
+            minimized.push(instruction);
+            continue;
+        }
+        resolvable.insert(instruction.output.clone());
+
+        if let Some(value) = instruction.expression.constant_value() {
+            instruction.expression = Expression::Assignment(Operand::Constant(value));
+        }
+        if let Expression::Assignment(operand) = &instruction.expression {
+            replacements.insert(instruction.output.clone(), operand.clone());
+            if !outputs.contains(&instruction.output.as_str()) {
+                continue;
+            }
+        }
+        minimized.push(instruction);
+    }
+
+    // only keep what the outputs depend on
+    let instructions_by_wire: HashMap<&str, &Instruction> = minimized
+        .iter()
+        .map(|instruction| (instruction.output.as_str(), instruction))
+        .collect();
+    let mut needed: HashSet<&str> = HashSet::new();
+    let mut unvisited: Vec<&str> = outputs.to_vec();
+    while let Some(wire) = unvisited.pop() {
+        if !needed.insert(wire) {
+            continue;
+        }
+        if let Some(instruction) = instructions_by_wire.get(wire) {
+            unvisited.extend(instruction.incoming_wires());
+        }
+    }
+    let needed: HashSet<String> = needed.into_iter().map(str::to_owned).collect();
+    minimized.retain(|instruction| needed.contains(&instruction.output));
+    minimized
+}
+
+/// Renders the circuit as a Graphviz digraph: wires are ellipses, gates have a shape per gate type
+/// and constants are plain-text leaves. Assignments become edges straight into their wire. With
+/// `wire_values`, every wire that has a signal is labelled with it
+pub fn to_dot(instructions: &[Instruction], wire_values: Option<&WireValues>) -> String {
+    let mut lines = vec!["digraph circuit {".to_owned(), "    rankdir=LR;".to_owned()];
+    let mut num_constants = 0;
+    let mut operand_node = |operand: &Operand, lines: &mut Vec<String>| match operand {
+        Operand::Constant(value) => {
+            num_constants += 1;
+            let id = format!("const{}", num_constants);
+            lines.push(format!(
+                "    \"{}\" [shape=plaintext, label=\"{}\"];",
+                id, value
+            ));
+            id
+        }
+        Operand::Wire(wire) => format!("wire:{}", wire),
+    };
+
+    for instruction in instructions {
+        let output = &instruction.output;
+        let label = match wire_values.and_then(|wire_values| wire_values.get(output)) {
+            Some(value) => format!("{}\\n{}", output, value),
+            None => output.clone(),
+        };
+        lines.push(format!(
+            "    \"wire:{}\" [shape=ellipse, label=\"{}\"];",
+            output, label
+        ));
+
+        let (gate, shape) = match &instruction.expression {
+            Expression::Assignment(operand) => {
+                let source = operand_node(operand, &mut lines);
+                lines.push(format!("    \"{}\" -> \"wire:{}\";", source, output));
+                continue;
+            }
+            Expression::Not(_) => ("NOT", "invtriangle"),
+            Expression::And { .. } => ("AND", "box"),
+            Expression::Or { .. } => ("OR", "diamond"),
+            Expression::LShift { .. } => ("LSHIFT", "larrow"),
+            Expression::RShift { .. } => ("RSHIFT", "rarrow"),
+        };
+        let gate_id = format!("gate:{}", output);
+        lines.push(format!(
+            "    \"{}\" [shape={}, label=\"{}\"];",
+            gate_id, shape, gate
+        ));
+        for operand in instruction.expression.operands() {
+            let source = operand_node(operand, &mut lines);
+            lines.push(format!("    \"{}\" -> \"{}\";", source, gate_id));
         }
+        lines.push(format!("    \"{}\" -> \"wire:{}\";", gate_id, output));
     }
+    lines.push("}".to_owned());
+    lines.join("\n") + "\n"
 }
 
 #[cfg(test)]
@@ -586,4 +998,267 @@ mod tests {
 
         assert_eq!(*values.get("g").unwrap(), 6);
     }
+
+    fn parse_instructions(lines: &[&str]) -> Vec<Instruction> {
+        lines.iter().map(|line| line.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn evaluator() {
+        let instructions = parse_instructions(&[
+            "x AND y -> d",
+            "x OR y -> e",
+            "x LSHIFT 2 -> f",
+            "y RSHIFT 2 -> g",
+            "NOT x -> h",
+            "NOT y -> i",
+            "123 -> x",
+            "456 -> y",
+        ]);
+        let mut evaluator = Evaluator::new(&instructions).unwrap();
+        assert_eq!(evaluator.wire_value("d"), Ok(72));
+        assert_eq!(
+            evaluator.wire_values(["e", "f", "g", "h", "i"]).unwrap(),
+            WireValues::from([
+                ("e".to_owned(), 507),
+                ("f".to_owned(), 492),
+                ("g".to_owned(), 114),
+                ("h".to_owned(), 65412),
+                ("i".to_owned(), 65079),
+            ])
+        );
+
+        evaluator.set_override("x", 1).unwrap();
+        assert_eq!(evaluator.wire_value("d"), Ok(0));
+        assert_eq!(evaluator.wire_value("x"), Ok(1));
+        assert_eq!(
+            evaluator.wire_value("z"),
+            Err(EvaluationError::UndefinedWire("z".to_owned()))
+        );
+    }
+
+    #[test]
+    fn incremental_overrides() {
+        let instructions = parse_instructions(&[
+            "x AND y -> d",
+            "d AND 8 -> e",
+            "NOT y -> f",
+            "f -> g",
+            "123 -> x",
+            "456 -> y",
+            "7 -> z",
+        ]);
+        let mut evaluator = Evaluator::new(&instructions).unwrap();
+        assert_eq!(evaluator.wire_values(["e", "g"]).unwrap()["e"], 8);
+
+        // x feeds into d and e, but only d changes as e only looks at a bit that stays set
+        let changes = evaluator.apply_overrides([("x", 59)]).unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                WireChange {
+                    wire: "d".to_owned(),
+                    old_value: 72,
+                    new_value: 8,
+                },
+                WireChange {
+                    wire: "x".to_owned(),
+                    old_value: 123,
+                    new_value: 59,
+                },
+            ]
+        );
+        assert_eq!(evaluator.wire_value("e"), Ok(8));
+        assert_eq!(evaluator.wire_value("g"), Ok(65079));
+
+        // z hasn't been computed yet, so there's nothing to report for it
+        assert_eq!(evaluator.apply_overrides([("z", 0), ("x", 59)]), Ok(vec![]));
+        assert_eq!(evaluator.wire_value("z"), Ok(0));
+
+        let changes = evaluator.apply_overrides([("y", 0)]).unwrap();
+        let changed_wires: Vec<_> = changes.iter().map(|change| change.wire.as_str()).collect();
+        assert_eq!(changed_wires, ["d", "e", "f", "g", "y"]);
+        assert_eq!(evaluator.wire_value("e"), Ok(0));
+    }
+
+    #[test]
+    fn evaluator_errors() {
+        let instructions = parse_instructions(&["b -> a", "c AND 1 -> b", "NOT b -> c", "1 -> d"]);
+        let mut evaluator = Evaluator::new(&instructions).unwrap();
+        assert_eq!(evaluator.wire_value("d"), Ok(1));
+        let error = evaluator.wire_value("a").unwrap_err();
+        assert_eq!(
+            error,
+            EvaluationError::Cycle(vec!["b".to_owned(), "c".to_owned(), "b".to_owned()])
+        );
+        assert_eq!(error.to_string(), "cycle: b -> c -> b");
+
+        // overriding a wire of the loop breaks it
+        evaluator.set_override("c", 3).unwrap();
+        assert_eq!(evaluator.wire_value("a"), Ok(1));
+
+        let instructions = parse_instructions(&["x -> y", "x AND q -> z"]);
+        let mut evaluator = Evaluator::new(&instructions).unwrap();
+        assert_eq!(
+            evaluator.wire_value("z"),
+            Err(EvaluationError::UndefinedWire("x".to_owned()))
+        );
+
+        let instructions = parse_instructions(&["1 -> a", "2 -> a"]);
+        assert_eq!(
+            Evaluator::new(&instructions).unwrap_err(),
+            EvaluationError::ConflictingDrivers("a".to_owned())
+        );
+
+        let instructions = parse_instructions(&["123 -> x", "16 -> y", "x LSHIFT y -> a"]);
+        let mut evaluator = Evaluator::new(&instructions).unwrap();
+        let error = evaluator.wire_value("a").unwrap_err();
+        assert_eq!(error, EvaluationError::ShiftOutOfRange(ShiftOutOfRange(16)));
+        assert_eq!(error.to_string(), "can't shift by 16 bits, only by 0 to 15");
+        assert_eq!(
+            Emulator::new().execute_instructions(&instructions),
+            Err("can't shift by 16 bits, only by 0 to 15".to_owned())
+        );
+        assert_eq!(
+            "x RSHIFT 15 -> a".parse::<Instruction>().map(|_| ()),
+            Ok(())
+        );
+        assert_eq!(
+            "x LSHIFT 16 -> a".parse::<Instruction>(),
+            Err("can't shift by 16 bits, only by 0 to 15".to_owned())
+        );
+        assert!(Day07::new("123 -> x\nx LSHIFT 16 -> a").is_err());
+
+        // overriding a shift amount can make a signal that was computed fine impossible to compute
+        let instructions = parse_instructions(&["3 -> y", "2 -> x", "x LSHIFT y -> a"]);
+        let mut evaluator = Evaluator::new(&instructions).unwrap();
+        assert_eq!(evaluator.wire_value("a"), Ok(16));
+        assert_eq!(
+            evaluator.apply_overrides([("y", 16)]),
+            Err(EvaluationError::ShiftOutOfRange(ShiftOutOfRange(16)))
+        );
+        let mut params = Params::default();
+        params.set("override_wire", "y");
+        let day = Day07::with_params("3 -> y\n2 -> x\nx LSHIFT y -> a", &params).unwrap();
+        assert_eq!(day.solve_part1(), Ok(16));
+        assert!(day.solve_part2().is_err());
+    }
+
+    #[test]
+    fn dot_export() {
+        let instructions = parse_instructions(&["123 -> x", "x AND 3 -> d", "d -> e"]);
+        let mut evaluator = Evaluator::new(&instructions).unwrap();
+        evaluator.wire_value("e").unwrap();
+        let wire_values = evaluator.into_wire_values();
+        assert_eq!(
+            to_dot(&instructions, Some(&wire_values)),
+            [
+                "digraph circuit {",
+                "    rankdir=LR;",
+                "    \"wire:x\" [shape=ellipse, label=\"x\\n123\"];",
+                "    \"const1\" [shape=plaintext, label=\"123\"];",
+                "    \"const1\" -> \"wire:x\";",
+                "    \"wire:d\" [shape=ellipse, label=\"d\\n3\"];",
+                "    \"gate:d\" [shape=box, label=\"AND\"];",
+                "    \"wire:x\" -> \"gate:d\";",
+                "    \"const2\" [shape=plaintext, label=\"3\"];",
+                "    \"const2\" -> \"gate:d\";",
+                "    \"gate:d\" -> \"wire:d\";",
+                "    \"wire:e\" [shape=ellipse, label=\"e\\n3\"];",
+                "    \"wire:d\" -> \"wire:e\";",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn instruction_display() {
+        for line in [
+            "x AND y -> d",
+            "1 OR y -> e",
+            "x LSHIFT 2 -> f",
+            "y RSHIFT 2 -> g",
+            "NOT x -> h",
+            "123 -> x",
+            "x -> y",
+        ] {
+            assert_eq!(line.parse::<Instruction>().unwrap().to_string(), line);
+        }
+    }
+
+    #[test]
+    fn minimization() {
+        let instructions = parse_instructions(&[
+            "x AND y -> d",
+            "d -> e",
+            "e -> f",
+            "NOT f -> g",
+            "g OR input -> out",
+            "123 -> x",
+            "456 -> y",
+            "x RSHIFT 1 -> unused",
+            "p -> q",
+            "q -> p",
+            "q OR 1 -> loop",
+            "1 -> input",
+        ]);
+
+        let minimized = minimize(&instructions, &["out"]);
+        assert_eq!(
+            minimized
+                .iter()
+                .map(|instruction| instruction.to_string())
+                .collect::<Vec<_>>(),
+            vec!["65463 -> out"]
+        );
+
+        // without a value, input stays a wire; the chain d -> e -> f collapses into g's constant
+        let without_input: Vec<_> = instructions
+            .iter()
+            .filter(|instruction| instruction.output != "input")
+            .cloned()
+            .collect();
+        let minimized = minimize(&without_input, &["out", "loop", "e"]);
+        let mut lines: Vec<_> = minimized
+            .iter()
+            .map(|instruction| instruction.to_string())
+            .collect();
+        lines.sort();
+        assert_eq!(
+            lines,
+            vec![
+                "65463 OR input -> out",
+                "72 -> e",
+                "p -> q",
+                "q -> p",
+                "q OR 1 -> loop"
+            ]
+        );
+    }
+
+    #[test]
+    fn minimization_preserves_signals() {
+        let mut instructions = parse_instructions(&[
+            "b -> a",
+            "c OR d -> b",
+            "NOT e -> c",
+            "e LSHIFT 2 -> d",
+            "f -> e",
+            "12 AND g -> f",
+            "h -> g",
+            "5 -> h",
+            "NOT a -> unused",
+        ]);
+        let minimized = minimize(&instructions, &["a", "d"]);
+        assert!(minimized.len() < instructions.len());
+
+        instructions.sort_topologically();
+        let original_values = Emulator::new().execute_instructions(&instructions).unwrap();
+        let minimized_values = Emulator::new().execute_instructions(&minimized).unwrap();
+        for wire in ["a", "d"] {
+            assert_eq!(original_values[wire], minimized_values[wire]);
+        }
+    }
 }
@@ -1,7 +1,5 @@
-use std::iter::Peekable;
-use std::str::Chars;
-
-use super::Challenge;
+use super::parsing::{ParseError, Parser};
+use super::{Challenge, ChallengeResult, LineError};
 
 pub struct Day08 {
     lines: Vec<String>,
@@ -12,130 +10,96 @@ impl Challenge for Day08 {
     type Part1Solution = usize;
     type Part2Solution = usize;
 
-    fn new(input: &str) -> Self {
-        Self {
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Ok(Self {
             lines: input.lines().map(|line| line.to_owned()).collect(),
-        }
+        })
     }
-    fn solve_part1(&self) -> Self::Part1Solution {
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
         self.lines
             .iter()
-            .map(|line| encoding_overhead(line).unwrap())
+            .zip(1..)
+            .map(|(line, line_number)| {
+                encoding_overhead(line).map_err(|error| error.at_line(line_number))
+            })
             .sum()
     }
-    fn solve_part2(&self) -> Self::Part2Solution {
-        self.lines.iter().map(|line| escape(line).len() - line.len()).sum()
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        Ok(self
+            .lines
+            .iter()
+            .map(|line| escape(line).len() - line.len())
+            .sum())
     }
 }
 
-type ParseError = String;
-
 fn encoding_overhead(input: &str) -> Result<usize, ParseError> {
     Ok(input.len() - parse(input)?.len())
 }
 
 fn parse(input: &str) -> Result<String, ParseError> {
-    Parser::new(input).parse()
+    StringLiteralParser::new(input).parse()
 }
 
-struct Parser<'a> {
-    input: Peekable<Chars<'a>>,
+struct StringLiteralParser<'a> {
+    parser: Parser<'a>,
 }
 
-impl<'a> Parser<'a> {
+impl<'a> StringLiteralParser<'a> {
     pub fn new(input: &'a str) -> Self {
-        Parser {
-            input: input.chars().peekable(),
+        StringLiteralParser {
+            parser: Parser::new(input),
         }
     }
 
     pub fn parse(mut self) -> Result<String, ParseError> {
-        self.expect_char('"')?;
+        self.parser.expect_literal("\"")?;
         let string = self.parse_string()?;
-        self.expect_char('"')?;
-        self.expect_end()?;
+        self.parser.expect_literal("\"")?;
+        self.parser.expect_end()?;
         Ok(string)
     }
 
-    fn expect_char(&mut self, expected: char) -> Result<char, ParseError> {
-        match self.input.next() {
-            None => Err(format!("input ended while expecting `{}`", expected)),
-            Some(c) => {
-                if c == expected {
-                    Ok(c)
-                } else {
-                    Err(format!("expected `{}`, got `{}`", expected, c))
-                }
-            }
-        }
-    }
-
-    fn expect_hex_digit(&mut self) -> Result<char, ParseError> {
-        match self.input.next() {
-            None => Err("input ended while expecting hex digit".into()),
-            Some(c) => {
-                if c.is_ascii_hexdigit() {
-                    Ok(c)
-                } else {
-                    Err(format!("expected hex digit, got `{}`", c))
-                }
-            }
-        }
-    }
-
-    fn expect_end(&mut self) -> Result<(), ParseError> {
-        match self.input.next() {
-            Some(_) => Err("input did not end after first string".into()),
-            None => Ok(()),
-        }
-    }
-
     fn parse_string(&mut self) -> Result<String, ParseError> {
         let mut string = String::new();
         loop {
-            match *self
-                .input
-                .peek()
-                .ok_or(String::from("input ended while parsing string"))?
-            {
-                '"' => {
-                    break;
-                }
-                '\\' => {
-                    string.push(self.parse_escape_sequence()?);
-                }
-                _ => {
-                    string.push(self.input.next().unwrap());
-                }
+            match self.parser.peek_char() {
+                None => return Err(self.parser.error("unterminated string")),
+                Some('"') => break,
+                Some('\\') => string.push(self.parse_escape_sequence()?),
+                Some(_) => string.push(self.parser.next_char().unwrap()),
             }
         }
         Ok(string)
     }
 
     fn parse_escape_sequence(&mut self) -> Result<char, ParseError> {
-        self.expect_char('\\')?;
-        let c = self
-            .input
-            .next()
-            .ok_or(String::from("input ended while parsing escape sequence"))?;
-        match c {
-            '"' | '\\' => Ok(c),
-            'x' => {
-                let hex_byte = self.parse_hex_byte()?;
-                Ok(if hex_byte < 128 {
-                    char::from_u32(hex_byte as u32).unwrap()
-                } else {
-                    '_' // DIRTY HACK! I should really use bytes instead of strings..
-                })
-            }
-            _ => Err(format!("invalid escaped character: `{}`", c)),
+        let start = self.parser.offset();
+        self.parser.expect_literal("\\")?;
+        if self.parser.try_literal("\"") {
+            Ok('"')
+        } else if self.parser.try_literal("\\") {
+            Ok('\\')
+        } else if self.parser.try_literal("x") {
+            let hex_byte = self.parse_hex_byte()?;
+            Ok(if hex_byte < 128 {
+                char::from_u32(hex_byte as u32).unwrap()
+            } else {
+                '_' // DIRTY HACK! I should really use bytes instead of strings..
+            })
+        } else {
+            Err(self.parser.error_at(start, "invalid escape sequence"))
         }
     }
 
     fn parse_hex_byte(&mut self) -> Result<u8, ParseError> {
         let mut hex_string = String::with_capacity(2);
-        hex_string.push(self.expect_hex_digit()?);
-        hex_string.push(self.expect_hex_digit()?);
+        for _ in 0..2 {
+            hex_string.push(
+                self.parser
+                    .expect_char("hex digit", |c| c.is_ascii_hexdigit())?,
+            );
+        }
         Ok(u8::from_str_radix(&hex_string, 16).unwrap())
     }
 }
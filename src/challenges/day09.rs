@@ -1,7 +1,7 @@
 use itertools::Itertools;
 use std::{collections::HashMap, str::FromStr};
 
-use super::Challenge;
+use super::{parse_lines, Challenge, ChallengeError, ChallengeResult};
 
 pub struct Day09 {
     map: Map,
@@ -13,22 +13,22 @@ impl Challenge for Day09 {
     type Part1Solution = u32;
     type Part2Solution = u32;
 
-    fn new(input: &str) -> Self {
-        Self {
-            map: Map::from_intercity_distances(
-                input
-                    .lines()
-                    .map(|line| line.parse::<IntercityDistance>().unwrap()),
-            ),
-        }
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Ok(Self {
+            map: Map::from_intercity_distances(parse_lines::<IntercityDistance>(input)?),
+        })
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
-        self.map.find_shortest_route().unwrap()
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        self.map
+            .find_shortest_route()
+            .ok_or_else(|| ChallengeError::new("no route visits every city"))
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
-        self.map.find_longest_route().unwrap()
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        self.map
+            .find_longest_route()
+            .ok_or_else(|| ChallengeError::new("no route visits every city"))
     }
 }
 
@@ -87,11 +87,11 @@ impl Map {
     pub fn add_intercity_distance(&mut self, distance: IntercityDistance) {
         self.distances
             .entry(distance.city1.clone())
-            .or_insert(HashMap::new())
+            .or_default()
             .insert(distance.city2.clone(), distance.distance);
         self.distances
             .entry(distance.city2)
-            .or_insert(HashMap::new())
+            .or_default()
             .insert(distance.city1, distance.distance);
     }
 
@@ -111,12 +111,10 @@ impl Map {
     }
 
     fn route_length(&self, cities: Vec<&str>) -> Option<u32> {
-        Some(
-            cities
-                .windows(2)
-                .map(|pair| self.distance_between(pair[0], pair[1]))
-                .sum::<Option<u32>>()?,
-        )
+        cities
+            .windows(2)
+            .map(|pair| self.distance_between(pair[0], pair[1]))
+            .sum::<Option<u32>>()
     }
 
     pub fn find_shortest_route(&self) -> Option<u32> {
@@ -1,9 +1,21 @@
 use std::{iter::Peekable, str::Chars};
 
-use super::Challenge;
+use super::{Challenge, ChallengeResult};
+use crate::params::{day_params, Params};
 
 pub struct Day10 {
     input: String,
+    params: Day10Params,
+}
+
+day_params! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Day10Params {
+        /// How many times to apply look-and-say in part 1
+        part1_iterations: usize = 40,
+        /// How many times to apply look-and-say in part 2
+        part2_iterations: usize = 50,
+    }
 }
 
 impl Challenge for Day10 {
@@ -12,23 +24,30 @@ impl Challenge for Day10 {
     type Part1Solution = usize;
     type Part2Solution = usize;
 
-    fn new(input: &str) -> Self {
-        Self {
+    const PARAMS: &'static [&'static str] = Day10Params::NAMES;
+
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Self::with_params(input, &Params::default())
+    }
+
+    fn with_params(input: &str, params: &Params) -> ChallengeResult<Self> {
+        Ok(Self {
             input: input.trim().to_owned(),
-        }
+            params: Day10Params::from_params(params)?,
+        })
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
-        look_and_say_n_times(&self.input, 40).len()
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        Ok(look_and_say_n_times(&self.input, self.params.part1_iterations).len())
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
-        look_and_say_n_times(&self.input, 50).len()
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        Ok(look_and_say_n_times(&self.input, self.params.part2_iterations).len())
     }
 }
 
 fn look_and_say(input: &str) -> String {
-    Scanner::new(input).scan().unwrap_or(String::new())
+    Scanner::new(input).scan().unwrap_or_default()
 }
 
 fn look_and_say_n_times(input: &str, n: usize) -> String {
@@ -1,7 +1,7 @@
-use super::Challenge;
+use super::{Challenge, ChallengeError, ChallengeResult};
 
 pub struct Day11 {
-    input: String,
+    password: Password,
 }
 
 impl Challenge for Day11 {
@@ -11,27 +11,28 @@ impl Challenge for Day11 {
 
     type Part2Solution = String;
 
-    fn new(input: &str) -> Self {
-        Self {
-            input: input.trim().to_owned(),
-        }
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Ok(Self {
+            password: Password::new(input.trim())
+                .map_err(|error| ChallengeError::at_line(1, error))?,
+        })
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
-        let mut password = Password::new(&self.input).unwrap();
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        let mut password = self.password.clone();
         password.increment_until_valid();
-        password.as_str().to_owned()
+        Ok(password.as_str().to_owned())
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
-        let mut password = Password::new(&self.input).unwrap();
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        let mut password = self.password.clone();
         password.increment_until_valid();
         password.increment_until_valid();
-        password.as_str().to_owned()
+        Ok(password.as_str().to_owned())
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct Password {
     inner: [u8; 8],
 }
@@ -42,7 +43,7 @@ impl<'a> Password {
             .as_bytes()
             .try_into()
             .map_err(|_| "password must be 8 characters long".to_owned())?;
-        if inner.into_iter().all(|byte| (b'a'..=b'z').contains(&byte)) {
+        if inner.into_iter().all(|byte: u8| byte.is_ascii_lowercase()) {
             Ok(Self { inner })
         } else {
             Err("password may contain only lowercase ASCII letters".to_owned())
@@ -127,7 +128,7 @@ impl<'a> Password {
 
 impl std::cmp::PartialEq<&str> for Password {
     fn eq(&self, other: &&str) -> bool {
-        &self.inner == other.as_bytes()
+        self.inner == other.as_bytes()
     }
 }
 
@@ -1,6 +1,6 @@
 use serde_json::Value;
 
-use super::Challenge;
+use super::{Challenge, ChallengeError, ChallengeResult};
 
 pub struct Day12 {
     input: Value,
@@ -12,50 +12,59 @@ impl Challenge for Day12 {
     type Part1Solution = i64;
     type Part2Solution = i64;
 
-    fn new(input: &str) -> Self {
-        Self {
-            input: serde_json::from_str(input).unwrap(),
-        }
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Ok(Self {
+            input: serde_json::from_str(input).map_err(|error| {
+                // serde_json appends the location to its message, we report it separately
+                let message = error.to_string();
+                let message = message
+                    .rsplit_once(" at line ")
+                    .map_or(message.as_str(), |(message, _)| message);
+                ChallengeError::at(error.line(), error.column(), message)
+            })?,
+        })
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
-        sum_all_numbers(&self.input)
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        sum_all_numbers(&self.input).map_err(ChallengeError::new)
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
-        sum_all_numbers_ignoring_red(&self.input)
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        sum_all_numbers_ignoring_red(&self.input).map_err(ChallengeError::new)
     }
 }
 
-fn sum_all_numbers(value: &Value) -> i64 {
+type Error = String;
+
+fn number_as_i64(number: &serde_json::Number) -> Result<i64, Error> {
+    number
+        .as_i64()
+        .ok_or_else(|| format!("expected signed integer, got {}", number))
+}
+
+fn sum_all_numbers(value: &Value) -> Result<i64, Error> {
     match value {
-        Value::Number(number) => number.as_i64().expect("expected signed integer"),
-        Value::Array(array) => array.iter().map(|value| sum_all_numbers(value)).sum(),
-        Value::Object(object) => object.values().map(|value| sum_all_numbers(value)).sum(),
-        _ => 0,
+        Value::Number(number) => number_as_i64(number),
+        Value::Array(array) => array.iter().map(sum_all_numbers).sum(),
+        Value::Object(object) => object.values().map(sum_all_numbers).sum(),
+        _ => Ok(0),
     }
 }
 
-fn sum_all_numbers_ignoring_red(value: &Value) -> i64 {
+fn sum_all_numbers_ignoring_red(value: &Value) -> Result<i64, Error> {
     match value {
-        Value::Number(number) => number.as_i64().expect("expected signed integer"),
-        Value::Array(array) => array
-            .iter()
-            .map(|value| sum_all_numbers_ignoring_red(value))
-            .sum(),
+        Value::Number(number) => number_as_i64(number),
+        Value::Array(array) => array.iter().map(sum_all_numbers_ignoring_red).sum(),
         Value::Object(object) => {
             if object.values().any(|value| match value {
                 Value::String(string) => string == "red",
                 _ => false,
             }) {
-                0
+                Ok(0)
             } else {
-                object
-                    .values()
-                    .map(|value| sum_all_numbers_ignoring_red(value))
-                    .sum()
+                object.values().map(sum_all_numbers_ignoring_red).sum()
             }
         }
-        _ => 0,
+        _ => Ok(0),
     }
 }
@@ -1,7 +1,8 @@
 use itertools::Itertools;
 use std::{collections::HashMap, str::FromStr};
 
-use super::Challenge;
+use super::parsing::{ParseError, Parser};
+use super::{parse_lines, Challenge, ChallengeError, ChallengeResult, Explain};
 
 pub struct Day13 {
     opinion_registry: OpinionRegistry,
@@ -13,21 +14,51 @@ impl Challenge for Day13 {
     type Part1Solution = i32;
     type Part2Solution = i32;
 
-    fn new(input: &str) -> Self {
-        Self {
-            opinion_registry: OpinionRegistry::from_opinions(
-                input.lines().map(|line| line.parse().unwrap()),
-            ),
-        }
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Ok(Self {
+            opinion_registry: OpinionRegistry::from_opinions(parse_lines(input)?),
+        })
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
         self.opinion_registry
             .find_happiness_change_of_best_arrangement()
-            .unwrap()
+            .map_err(ChallengeError::new)
+    }
+
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        self.opinion_registry_with_me()
+            .find_happiness_change_of_best_arrangement()
+            .map_err(ChallengeError::new)
+    }
+}
+
+impl Explain for Day13 {
+    fn explain_part1(&self) -> ChallengeResult<(i32, String)> {
+        explain_best_arrangement(&self.opinion_registry)
+    }
+
+    fn explain_part2(&self) -> ChallengeResult<(i32, String)> {
+        explain_best_arrangement(&self.opinion_registry_with_me())
     }
+}
 
-    fn solve_part2(&self) -> Self::Part2Solution {
+fn explain_best_arrangement(opinion_registry: &OpinionRegistry) -> ChallengeResult<(i32, String)> {
+    let (arrangement, happiness_change) = opinion_registry
+        .find_best_arrangement()
+        .map_err(ChallengeError::new)?;
+    Ok((
+        happiness_change,
+        format!(
+            "seating {} around the table changes happiness by {}",
+            arrangement.join(", "),
+            happiness_change
+        ),
+    ))
+}
+
+impl Day13 {
+    fn opinion_registry_with_me(&self) -> OpinionRegistry {
         let mut opinion_registry = self.opinion_registry.clone();
         for guest in opinion_registry
             .guests()
@@ -46,8 +77,6 @@ impl Challenge for Day13 {
             });
         }
         opinion_registry
-            .find_happiness_change_of_best_arrangement()
-            .unwrap()
     }
 }
 
@@ -58,8 +87,6 @@ struct InterpersonalOpinion {
     happiness_change: i32,
 }
 
-type ParseError = String;
-
 impl FromStr for InterpersonalOpinion {
     type Err = ParseError;
 
@@ -69,23 +96,25 @@ impl FromStr for InterpersonalOpinion {
 }
 
 struct InterpersonalOpinionParser<'a> {
-    input: &'a str,
-    cursor: usize,
+    parser: Parser<'a>,
 }
 
 impl<'a> InterpersonalOpinionParser<'a> {
     fn new(input: &'a str) -> Self {
-        Self { input, cursor: 0 }
+        Self {
+            parser: Parser::new(input),
+        }
     }
 
     fn parse(mut self) -> Result<InterpersonalOpinion, ParseError> {
-        let subject = self.parse_name()?;
-        self.expect_literal(" would ")?;
+        let subject = self.parse_name();
+        self.parser.expect_literal(" would ")?;
         let happiness_change = self.parse_happiness_change()?;
-        self.expect_literal(" happiness units by sitting next to ")?;
-        let other = self.parse_name()?;
-        self.expect_literal(".")?;
-        self.expect_end()?;
+        self.parser
+            .expect_literal(" happiness units by sitting next to ")?;
+        let other = self.parse_name();
+        self.parser.expect_literal(".")?;
+        self.parser.expect_end()?;
         Ok(InterpersonalOpinion {
             subject,
             other,
@@ -93,59 +122,23 @@ impl<'a> InterpersonalOpinionParser<'a> {
         })
     }
 
-    fn remaining_input(&self) -> &str {
-        &self.input[self.cursor..]
-    }
-
-    fn take_while(&mut self, mut predicate: impl FnMut(char) -> bool) -> &str {
-        let last_cursor = self.cursor;
-        self.cursor = match self.input[last_cursor..].find(|c| !predicate(c)) {
-            Some(i) => self.cursor + i,
-            None => self.input.len(),
-        };
-        &self.input[last_cursor..self.cursor]
-    }
-
-    fn parse_name(&mut self) -> Result<String, ParseError> {
-        Ok(self.take_while(char::is_alphabetic).into())
+    fn parse_name(&mut self) -> String {
+        self.parser.take_while(char::is_alphabetic).into()
     }
 
     fn parse_happiness_change(&mut self) -> Result<i32, ParseError> {
         let sign = self.parse_sign()?;
-        self.expect_literal(" ")?;
-        let magnitude = self.parse_number()?;
+        self.parser.expect_literal(" ")?;
+        let magnitude: i32 = self.parser.parse_number()?;
         Ok(sign * magnitude)
     }
 
     fn parse_sign(&mut self) -> Result<i32, ParseError> {
-        match self.take_while(char::is_alphabetic) {
+        let start = self.parser.offset();
+        match self.parser.take_while(char::is_alphabetic) {
             "gain" => Ok(1),
             "lose" => Ok(-1),
-            _ => Err("invalid sign".to_owned()),
-        }
-    }
-
-    fn parse_number(&mut self) -> Result<i32, ParseError> {
-        Ok(self
-            .take_while(char::is_numeric)
-            .parse::<i32>()
-            .map_err(|_| "invalid number".to_owned())?)
-    }
-
-    fn expect_literal(&mut self, expected: &str) -> Result<(), ParseError> {
-        if !self.remaining_input().starts_with(expected) {
-            Err(format!("expected literal \"{}\"", expected))
-        } else {
-            self.cursor += expected.len();
-            Ok(())
-        }
-    }
-
-    fn expect_end(&self) -> Result<(), ParseError> {
-        if self.cursor == self.input.len() {
-            Ok(())
-        } else {
-            Err("input didn't end after valid parse".to_owned())
+            _ => Err(self.parser.error_at(start, "expected \"gain\" or \"lose\"")),
         }
     }
 }
@@ -230,10 +223,21 @@ impl OpinionRegistry {
     }
 
     fn find_happiness_change_of_best_arrangement(&self) -> Result<i32, String> {
-        self.possible_seating_arrangements()?
-            .map(|arrangement| self.happiness_change_of_arrangement(&arrangement))
-            .max()
-            .expect("no possible seating arrangements")
+        Ok(self.find_best_arrangement()?.1)
+    }
+
+    fn find_best_arrangement(&self) -> Result<(Vec<&str>, i32), String> {
+        let rated_arrangements = self
+            .possible_seating_arrangements()?
+            .map(|arrangement| {
+                let happiness_change = self.happiness_change_of_arrangement(&arrangement)?;
+                Ok((arrangement, happiness_change))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        rated_arrangements
+            .into_iter()
+            .max_by_key(|(_, happiness_change)| *happiness_change)
+            .ok_or_else(|| "no possible seating arrangements".to_owned())
     }
 }
 
@@ -313,6 +317,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::identity_op)]
     fn test_opinion_registry() {
         let opinions = [
             "Alice would gain 54 happiness units by sitting next to Bob.",
@@ -360,5 +365,38 @@ mod tests {
                 .unwrap(),
             330
         );
+
+        let (arrangement, happiness_change) = opinion_registry.find_best_arrangement().unwrap();
+        assert_eq!(happiness_change, 330);
+        assert_eq!(
+            opinion_registry.happiness_change_of_arrangement(&arrangement),
+            Ok(330)
+        );
+    }
+
+    #[test]
+    fn test_incomplete_opinions() {
+        // without Alice's opinion of David, only arrangements that keep them apart can be rated
+        let opinions = [
+            "Alice would gain 54 happiness units by sitting next to Bob.",
+            "Alice would lose 79 happiness units by sitting next to Carol.",
+            "Bob would gain 83 happiness units by sitting next to Alice.",
+            "Bob would lose 7 happiness units by sitting next to Carol.",
+            "Bob would lose 63 happiness units by sitting next to David.",
+            "Carol would lose 62 happiness units by sitting next to Alice.",
+            "Carol would gain 60 happiness units by sitting next to Bob.",
+            "Carol would gain 55 happiness units by sitting next to David.",
+            "David would gain 46 happiness units by sitting next to Alice.",
+            "David would lose 7 happiness units by sitting next to Bob.",
+            "David would gain 41 happiness units by sitting next to Carol.",
+        ]
+        .into_iter()
+        .map(|line| line.parse::<InterpersonalOpinion>().unwrap());
+
+        let opinion_registry = OpinionRegistry::from_opinions(opinions);
+        assert_eq!(
+            opinion_registry.find_best_arrangement(),
+            Err("incomplete opinion data".to_owned())
+        );
     }
 }
@@ -1,35 +1,52 @@
 use std::{cmp::min, str::FromStr};
 
-use super::Challenge;
+use super::parsing::{ParseError, Parser};
+use super::{parse_lines, Challenge, ChallengeError, ChallengeResult};
+use crate::params::{day_params, Params};
 
 pub struct Day14 {
     olympics: ReindeerOlympics,
 }
 
+day_params! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Day14Params {
+        /// How long the race lasts
+        seconds: u32 = 2503,
+    }
+}
+
 impl Challenge for Day14 {
     const DAY: u8 = 14;
 
     type Part1Solution = u32;
     type Part2Solution = u32;
 
-    fn new(input: &str) -> Self {
-        Self {
+    const PARAMS: &'static [&'static str] = Day14Params::NAMES;
+
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Self::with_params(input, &Params::default())
+    }
+
+    fn with_params(input: &str, params: &Params) -> ChallengeResult<Self> {
+        Ok(Self {
             olympics: ReindeerOlympics {
-                contestants: input
-                    .lines()
-                    .map(|line| line.parse::<ReindeerStats>().unwrap())
-                    .collect(),
-                race_duration: 2503,
+                contestants: parse_lines(input)?,
+                race_duration: Day14Params::from_params(params)?.seconds,
             },
-        }
+        })
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
-        self.olympics.race1_leading_distance_traveled()
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        self.olympics
+            .race1_leading_distance_traveled()
+            .ok_or_else(|| ChallengeError::new("no contestants"))
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
-        self.olympics.race2_leading_points()
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        self.olympics
+            .race2_leading_points()
+            .ok_or_else(|| ChallengeError::new("no contestants"))
     }
 }
 
@@ -39,15 +56,14 @@ struct ReindeerOlympics {
 }
 
 impl ReindeerOlympics {
-    fn race1_leading_distance_traveled(&self) -> u32 {
+    fn race1_leading_distance_traveled(&self) -> Option<u32> {
         self.contestants
             .iter()
             .map(|stats| RacingReindeer::distance_traveled_at(stats, self.race_duration))
             .max()
-            .expect("no contestants")
     }
 
-    fn race2_leading_points(&self) -> u32 {
+    fn race2_leading_points(&self) -> Option<u32> {
         let mut race2 = Race2::new(&self.contestants);
         race2.race_for(self.race_duration);
         race2.leading_points()
@@ -64,7 +80,7 @@ struct Race2<'a> {
 }
 
 impl<'a> Race2<'a> {
-    fn new(contestants: &'a Vec<ReindeerStats>) -> Self {
+    fn new(contestants: &'a [ReindeerStats]) -> Self {
         Self {
             contestants: contestants
                 .iter()
@@ -87,7 +103,7 @@ impl<'a> Race2<'a> {
                 .iter()
                 .map(|contestant| contestant.reindeer.distance_traveled)
                 .max()
-                .expect("no contestants");
+                .unwrap_or(0);
 
             for contestant in self.contestants.iter_mut() {
                 if contestant.reindeer.distance_traveled == leading_distance {
@@ -97,12 +113,11 @@ impl<'a> Race2<'a> {
         }
     }
 
-    fn leading_points(&self) -> u32 {
+    fn leading_points(&self) -> Option<u32> {
         self.contestants
             .iter()
             .map(|contestant| contestant.points)
             .max()
-            .expect("no contestants")
     }
 }
 
@@ -173,11 +188,8 @@ impl<'a> RacingReindeer<'a> {
     }
 }
 
-type MyError = String;
-type MyResult<T> = Result<T, MyError>;
-
 impl FromStr for ReindeerStats {
-    type Err = MyError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         ReindeerStatsParser::new(s).parse()
@@ -185,25 +197,27 @@ impl FromStr for ReindeerStats {
 }
 
 struct ReindeerStatsParser<'a> {
-    input: &'a str,
-    cursor: usize,
+    parser: Parser<'a>,
 }
 
 impl<'a> ReindeerStatsParser<'a> {
     pub fn new(input: &'a str) -> Self {
-        Self { input, cursor: 0 }
+        Self {
+            parser: Parser::new(input),
+        }
     }
 
-    pub fn parse(mut self) -> MyResult<ReindeerStats> {
-        let name = self.parse_name()?;
-        self.expect_literal(" can fly ")?;
-        let speed = self.parse_u32()?;
-        self.expect_literal(" km/s for ")?;
-        let flight_duration = self.parse_u32()?;
-        self.expect_literal(" seconds, but then must rest for ")?;
-        let rest_duration = self.parse_u32()?;
-        self.expect_literal(" seconds.")?;
-        self.expect_end()?;
+    pub fn parse(mut self) -> Result<ReindeerStats, ParseError> {
+        let name = self.parser.take_while(char::is_alphabetic).to_owned();
+        self.parser.expect_literal(" can fly ")?;
+        let speed = self.parser.parse_number()?;
+        self.parser.expect_literal(" km/s for ")?;
+        let flight_duration = self.parser.parse_number()?;
+        self.parser
+            .expect_literal(" seconds, but then must rest for ")?;
+        let rest_duration = self.parser.parse_number()?;
+        self.parser.expect_literal(" seconds.")?;
+        self.parser.expect_end()?;
         Ok(ReindeerStats {
             name,
             speed,
@@ -211,42 +225,6 @@ impl<'a> ReindeerStatsParser<'a> {
             rest_duration,
         })
     }
-
-    fn parse_name(&mut self) -> MyResult<String> {
-        Ok(self.take_while(char::is_alphabetic).to_owned())
-    }
-
-    fn parse_u32(&mut self) -> MyResult<u32> {
-        self.take_while(char::is_numeric)
-            .parse()
-            .map_err(|_| "could not parse u32".to_owned())
-    }
-
-    fn take_while(&mut self, mut predicate: impl FnMut(char) -> bool) -> &str {
-        let last_cursor = self.cursor;
-        self.cursor = match self.input[last_cursor..].find(|c| !predicate(c)) {
-            Some(i) => self.cursor + i,
-            None => self.input.len(),
-        };
-        &self.input[last_cursor..self.cursor]
-    }
-
-    fn expect_literal(&mut self, expected: &str) -> MyResult<()> {
-        if !self.input[self.cursor..].starts_with(expected) {
-            Err(format!("expected literal \"{}\"", expected))
-        } else {
-            self.cursor += expected.len();
-            Ok(())
-        }
-    }
-
-    fn expect_end(&self) -> MyResult<()> {
-        if self.cursor == self.input.len() {
-            Ok(())
-        } else {
-            Err("input didn't end after valid parse".to_owned())
-        }
-    }
 }
 
 #[cfg(test)]
@@ -314,6 +292,6 @@ mod tests {
         race2.race_for(1000);
         assert_eq!(race2.contestants[dancer_index].points, 689);
         assert_eq!(race2.contestants[comet_index].points, 312);
-        assert_eq!(race2.leading_points(), 689);
+        assert_eq!(race2.leading_points(), Some(689));
     }
 }
@@ -5,10 +5,22 @@ use std::{
     str::FromStr,
 };
 
-use super::Challenge;
+use super::{parse_lines, Challenge, ChallengeError, ChallengeResult};
+use crate::params::{day_params, Params};
 
 pub struct Day15 {
     recipe_optimizer: RecipeOptimizer,
+    params: Day15Params,
+}
+
+day_params! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Day15Params {
+        /// How many teaspoons of ingredients go into a cookie
+        teaspoons: u32 = 100,
+        /// How many calories a cookie must have in part 2
+        calories: i32 = 500,
+    }
 }
 
 impl Challenge for Day15 {
@@ -18,22 +30,34 @@ impl Challenge for Day15 {
 
     type Part2Solution = u32;
 
-    fn new(input: &str) -> Self {
-        Self {
-            recipe_optimizer: RecipeOptimizer::with_ingredients(
-                input
-                    .lines()
-                    .map(|line| line.parse::<Ingredient>().unwrap()),
-            ),
+    const PARAMS: &'static [&'static str] = Day15Params::NAMES;
+
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Self::with_params(input, &Params::default())
+    }
+
+    fn with_params(input: &str, params: &Params) -> ChallengeResult<Self> {
+        let ingredients = parse_lines::<Ingredient>(input)?;
+        if ingredients.is_empty() {
+            return Err(ChallengeError::new("no ingredients"));
         }
+        let params = Day15Params::from_params(params)?;
+        Ok(Self {
+            recipe_optimizer: RecipeOptimizer::new(ingredients, params.teaspoons),
+            params,
+        })
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
-        self.recipe_optimizer.optimal_recipe_score()
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        self.recipe_optimizer
+            .optimal_recipe_score()
+            .ok_or_else(|| ChallengeError::new("no valid recipes"))
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
-        self.recipe_optimizer.optimal_recipe_score_with_calories(500)
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        self.recipe_optimizer
+            .optimal_recipe_score_with_calories(self.params.calories)
+            .ok_or_else(|| ChallengeError::new("no valid recipes"))
     }
 }
 
@@ -106,24 +130,26 @@ impl Mul<Properties> for i32 {
 
 struct RecipeOptimizer {
     ingredients: Vec<Ingredient>,
+    teaspoons: u32,
 }
 
 impl RecipeOptimizer {
-    fn with_ingredients(ingredients: impl IntoIterator<Item = Ingredient>) -> Self {
+    /// Mixes `teaspoons` of the `ingredients` in total
+    fn new(ingredients: impl IntoIterator<Item = Ingredient>, teaspoons: u32) -> Self {
         Self {
             ingredients: ingredients.into_iter().collect(),
+            teaspoons,
         }
     }
 
-    fn optimal_recipe_score(&self) -> u32 {
-        AllPossibleMixes::new(100, self.ingredients.len())
+    fn optimal_recipe_score(&self) -> Option<u32> {
+        AllPossibleMixes::new(self.teaspoons, self.ingredients.len())
             .map(|mix| self.recipe_properties(mix).score())
             .max()
-            .expect("no valid recipes")
     }
 
-    fn optimal_recipe_score_with_calories(&self, expected: i32) -> u32 {
-        AllPossibleMixes::new(100, self.ingredients.len())
+    fn optimal_recipe_score_with_calories(&self, expected: i32) -> Option<u32> {
+        AllPossibleMixes::new(self.teaspoons, self.ingredients.len())
             .map(|mix| self.recipe_properties(mix))
             .filter_map(|properties| {
                 if properties.calories == expected {
@@ -133,7 +159,6 @@ impl RecipeOptimizer {
                 }
             })
             .max()
-            .expect("no valid recipes")
     }
 
     fn recipe_properties(&self, mix: Vec<u32>) -> Properties {
@@ -152,7 +177,7 @@ impl FromStr for Ingredient {
     type Err = Error;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        let (name, properties) = s.split_once(": ").ok_or_else(|| "no \": \" in input")?;
+        let (name, properties) = s.split_once(": ").ok_or("no \": \" in input")?;
         Ok(Self {
             name: name.trim().to_owned(),
             properties: properties.trim().parse::<Properties>()?,
@@ -183,7 +208,7 @@ fn parse_property<'a>(
         .next()
         .ok_or_else(|| format!("input ended while expecting property \"{}\"", expected))?
         .split_once(' ')
-        .ok_or_else(|| "expected property name and value to be separated by space")?;
+        .ok_or("expected property name and value to be separated by space")?;
 
     if name == expected {
         Ok(value
@@ -343,6 +368,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::neg_multiply)]
     fn test_property_score() {
         assert_eq!(
             Properties {
@@ -359,16 +385,20 @@ mod tests {
 
     #[test]
     fn test_recipe_optimizer() {
-        let optimizer = RecipeOptimizer::with_ingredients(
+        let optimizer = RecipeOptimizer::new(
             [
                 "Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8",
                 "Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3",
             ]
             .into_iter()
             .map(|line| line.parse::<Ingredient>().unwrap()),
+            Day15Params::default().teaspoons,
         );
 
-        assert_eq!(optimizer.optimal_recipe_score(), 62842880);
-        assert_eq!(optimizer.optimal_recipe_score_with_calories(500), 57600000);
+        assert_eq!(optimizer.optimal_recipe_score(), Some(62842880));
+        assert_eq!(
+            optimizer.optimal_recipe_score_with_calories(500),
+            Some(57600000)
+        );
     }
 }
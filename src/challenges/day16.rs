@@ -1,8 +1,8 @@
 use std::{collections::HashMap, str::FromStr};
 
-use super::Challenge;
+use super::{parse_lines, Challenge, ChallengeError, ChallengeResult};
 
-const SUPPLEMENTARY_INPUT: &'static str = "\
+const SUPPLEMENTARY_INPUT: &str = "\
     children: 3, \
     cats: 7, \
     samoyeds: 2, \
@@ -21,31 +21,21 @@ pub struct Day16 {
 }
 
 impl Day16 {
-    fn find_matching_sue<T: Fn(&str, u32, u32) -> bool + Copy>(&self, strategy: T) -> &Sue {
+    fn find_matching_sue<T: Fn(&str, u32, u32) -> bool + Copy>(&self, strategy: T) -> Result<&Sue> {
         let potential_matches = self
             .sues
             .iter()
-            .filter_map(|sue| {
-                if sue.attributes.matches_reference(&self.reference, strategy) {
-                    Some(sue)
-                } else {
-                    None
-                }
-            })
+            .filter(|sue| sue.attributes.matches_reference(&self.reference, strategy))
             .collect::<Vec<_>>();
 
         let strategy_name = std::any::type_name::<T>();
         match potential_matches.len() {
-            0 => {
-                panic!("no Sues match using strategy {}", strategy_name)
-            }
-            1 => potential_matches[0],
-            _ => {
-                panic!(
-                    "expected exactly one match using strategy {}, got: {:?}",
-                    strategy_name, potential_matches
-                )
-            }
+            0 => Err(format!("no Sues match using strategy {}", strategy_name)),
+            1 => Ok(potential_matches[0]),
+            _ => Err(format!(
+                "expected exactly one match using strategy {}, got: {:?}",
+                strategy_name, potential_matches
+            )),
         }
     }
 }
@@ -56,22 +46,25 @@ impl Challenge for Day16 {
     type Part1Solution = u32;
     type Part2Solution = u32;
 
-    fn new(input: &str) -> Self {
-        Self {
-            reference: SUPPLEMENTARY_INPUT.parse::<Attributes>().unwrap(),
-            sues: input
-                .lines()
-                .map(|line| line.parse::<Sue>().unwrap())
-                .collect(),
-        }
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Ok(Self {
+            reference: SUPPLEMENTARY_INPUT
+                .parse::<Attributes>()
+                .map_err(ChallengeError::new)?,
+            sues: parse_lines(input)?,
+        })
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
-        self.find_matching_sue(part1_attribute_matches).id
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        self.find_matching_sue(part1_attribute_matches)
+            .map(|sue| sue.id)
+            .map_err(ChallengeError::new)
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
-        self.find_matching_sue(part2_attribute_matches).id
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        self.find_matching_sue(part2_attribute_matches)
+            .map(|sue| sue.id)
+            .map_err(ChallengeError::new)
     }
 }
 
@@ -144,7 +137,7 @@ impl FromStr for Sue {
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         let (sue_id, attributes) = s
             .split_once(':')
-            .ok_or_else(|| "expected sue id and attributes delimited by ':'")?;
+            .ok_or("expected sue id and attributes delimited by ':'")?;
         Ok(Sue {
             id: parse_sue_id(sue_id)?,
             attributes: attributes.parse::<Attributes>()?,
@@ -168,14 +161,14 @@ impl FromStr for Attributes {
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         Ok(Self::with_attributes(
             s.split(',')
-                .map(|attribute| parse_attribute(attribute))
+                .map(parse_attribute)
                 .collect::<Result<Vec<_>>>()?,
         ))
     }
 }
 
 fn parse_attribute(s: &str) -> Result<(String, u32)> {
-    let (name, value) = s.split_once(':').ok_or_else(|| "expected ':'")?;
+    let (name, value) = s.split_once(':').ok_or("expected ':'")?;
     Ok((
         name.trim().to_owned(),
         value
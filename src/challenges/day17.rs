@@ -1,9 +1,19 @@
 use std::cmp::Ordering;
 
-use super::Challenge;
+use super::{parse_lines, Challenge, ChallengeError, ChallengeResult};
+use crate::params::{day_params, Params};
 
 pub struct Day17 {
     available_items: Vec<u32>,
+    params: Day17Params,
+}
+
+day_params! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Day17Params {
+        /// How much eggnog has to be stored
+        liters: u32 = 150,
+    }
 }
 
 impl Challenge for Day17 {
@@ -12,31 +22,35 @@ impl Challenge for Day17 {
     type Part1Solution = usize;
     type Part2Solution = usize;
 
-    fn new(input: &str) -> Self {
-        Self {
-            available_items: input
-                .lines()
-                .map(|line| line.parse::<u32>().unwrap())
-                .collect(),
-        }
+    const PARAMS: &'static [&'static str] = Day17Params::NAMES;
+
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Self::with_params(input, &Params::default())
+    }
+
+    fn with_params(input: &str, params: &Params) -> ChallengeResult<Self> {
+        Ok(Self {
+            available_items: parse_lines(input)?,
+            params: Day17Params::from_params(params)?,
+        })
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
-        AllPossibleDistributions::new(150, &self.available_items).count()
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        Ok(AllPossibleDistributions::new(self.params.liters, &self.available_items).count())
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
         let all_possible_distributions: Vec<_> =
-            AllPossibleDistributions::new(150, &self.available_items).collect();
+            AllPossibleDistributions::new(self.params.liters, &self.available_items).collect();
         let min_length = all_possible_distributions
             .iter()
             .map(|distribution| distribution.len())
             .min()
-            .expect("no possible solutions");
-        all_possible_distributions
+            .ok_or_else(|| ChallengeError::new("no possible solutions"))?;
+        Ok(all_possible_distributions
             .iter()
             .filter(|distribution| distribution.len() == min_length)
-            .count()
+            .count())
     }
 }
 
@@ -90,7 +104,7 @@ impl<'a> AllPossibleDistributions<'a> {
     }
 
     fn current(&self) -> Option<Vec<u32>> {
-        if self.current_indices.len() == 0 {
+        if self.current_indices.is_empty() {
             None
         } else {
             Some(
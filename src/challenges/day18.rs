@@ -1,9 +1,19 @@
 use std::str::FromStr;
 
-use super::Challenge;
+use super::{Challenge, ChallengeError, ChallengeResult};
+use crate::params::{day_params, Params};
 
 pub struct Day18 {
     initial_grid: Grid,
+    params: Day18Params,
+}
+
+day_params! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Day18Params {
+        /// How many animation steps to run
+        steps: u32 = 100,
+    }
 }
 
 impl Challenge for Day18 {
@@ -12,23 +22,30 @@ impl Challenge for Day18 {
     type Part1Solution = usize;
     type Part2Solution = usize;
 
-    fn new(input: &str) -> Self {
-        Self {
-            initial_grid: input.parse::<Grid>().unwrap(),
-        }
+    const PARAMS: &'static [&'static str] = Day18Params::NAMES;
+
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Self::with_params(input, &Params::default())
+    }
+
+    fn with_params(input: &str, params: &Params) -> ChallengeResult<Self> {
+        Ok(Self {
+            initial_grid: input.parse::<Grid>().map_err(ChallengeError::new)?,
+            params: Day18Params::from_params(params)?,
+        })
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
         let mut lights = Lights::new(self.initial_grid.clone());
-        lights.animate(100);
-        lights.count_on()
+        lights.animate(self.params.steps);
+        Ok(lights.count_on())
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
         let mut lights = Lights::new(self.initial_grid.clone());
         lights.set_corners_always_on();
-        lights.animate(100);
-        lights.count_on()
+        lights.animate(self.params.steps);
+        Ok(lights.count_on())
     }
 }
 
@@ -85,8 +102,8 @@ impl Grid {
         Coordinates::new(self.x_len, self.y_len)
     }
 
-    fn neighbors(&self, x: usize, y: usize) -> Neighbors {
-        Neighbors::new(&self, x, y)
+    fn neighbors(&self, x: usize, y: usize) -> Neighbors<'_> {
+        Neighbors::new(self, x, y)
     }
 
     fn turn_on_corners(&mut self) {
@@ -293,7 +310,7 @@ mod tests {
 
     use super::*;
 
-    const INITIAL_STATE: &'static str = "
+    const INITIAL_STATE: &str = "
         .#.#.#
         ...##.
         #....#
@@ -2,7 +2,7 @@ use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::rc::Rc;
 use std::str::FromStr;
 
-use super::Challenge;
+use super::{parse_lines, Challenge, ChallengeError, ChallengeResult, Explain};
 
 pub struct Day19 {
     replacements: Vec<Replacement>,
@@ -15,40 +15,68 @@ impl Challenge for Day19 {
     type Part1Solution = usize;
     type Part2Solution = usize;
 
-    fn new(input: &str) -> Self {
+    fn new(input: &str) -> ChallengeResult<Self> {
         let mut lines = input.lines();
         let replacements: Vec<_> = lines
             .by_ref()
-            .take_while(|line| !line.is_empty())
-            .map(|line| line.parse::<Replacement>().unwrap())
+            .take_while(|line| !line.trim().is_empty())
             .collect();
+        let replacements = parse_lines::<Replacement>(&replacements.join("\n"))?;
+        if replacements.is_empty() {
+            return Err(ChallengeError::at_line(1, "no replacements"));
+        }
         let input_molecule = lines
-            .next()
-            .expect("expected input molecule following blank line")
-            .to_owned();
-        Self {
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .ok_or_else(|| ChallengeError::new("expected input molecule following blank line"))?;
+        Ok(Self {
             replacements,
-            input_molecule,
-        }
+            input_molecule: input_molecule.to_owned(),
+        })
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
         let machine = Machine::with_replacements(self.replacements.clone());
-        machine.calibrate(self.input_molecule.clone()).len()
+        Ok(machine.calibrate(self.input_molecule.clone()).len())
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
         let machine = Machine::with_replacements(self.replacements.clone());
         machine
             .optimal_recipe_a_star(self.input_molecule.clone())
-            .expect("no valid recipes")
-        // machine
-        //     .optimal_recipe_len(&self.input_molecule)
-        //     .expect("no valid recipes")
-        // machine
-        //     .optimal_recipe(self.input_molecule.clone())
-        //     .expect("no valid recipes")
-        //     .len()
+            .ok_or_else(|| ChallengeError::new("no valid recipes"))
+    }
+}
+
+impl Explain for Day19 {
+    fn explain_part1(&self) -> ChallengeResult<(usize, String)> {
+        let molecules: Vec<_> = PossibleTransformations::new(
+            &self.replacements,
+            self.input_molecule.clone(),
+            Direction::Forward,
+        )
+        .map(|(molecule, _)| molecule)
+        .collect();
+        let num_distinct_molecules = molecules.iter().collect::<HashSet<_>>().len();
+        Ok((
+            num_distinct_molecules,
+            format!(
+                "{} single replacements are possible, producing {} distinct molecules",
+                molecules.len(),
+                num_distinct_molecules
+            ),
+        ))
+    }
+
+    fn explain_part2(&self) -> ChallengeResult<(usize, String)> {
+        let machine = Machine::with_replacements(self.replacements.clone());
+        let recipe = machine
+            .optimal_recipe_path(self.input_molecule.clone())
+            .ok_or_else(|| ChallengeError::new("no valid recipes"))?;
+        Ok((
+            recipe.len(),
+            format_recipe(&self.input_molecule, &recipe, &self.replacements),
+        ))
     }
 }
 
@@ -77,7 +105,7 @@ impl FromStr for Replacement {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (pattern, result) = s.split_once(" => ").ok_or_else(|| "expected \" => \"")?;
+        let (pattern, result) = s.split_once(" => ").ok_or("expected \" => \"")?;
         Ok(Self {
             pattern: pattern.to_owned(),
             result: result.to_owned(),
@@ -115,76 +143,37 @@ impl Machine {
             .collect()
     }
 
-    fn recipes<'a>(&'a self, target: String) -> Recipes<'a> {
-        Recipes::new(&self.replacements, target)
-    }
-
-    fn optimal_recipe(&self, target: String) -> Option<Vec<TransformationInfo>> {
-        // FIXME: this doesn't compute the optimal recipe, just the first recipe it finds!
-        Some(
-            self.recipes(target.clone())
-                .inspect(|solution| print_solution(&target, solution, &self.replacements))
-                .inspect(|solution| println!("Solution length: {}", solution.len()))
-                .next()?, // .min_by_key(|recipe| recipe.len())?,
-        )
-    }
-
     fn optimal_recipe_a_star(&self, target: String) -> Option<usize> {
-        RecipeFinder::new(target, &self.replacements).find_shortest_path()
+        self.optimal_recipe_path(target).map(|recipe| recipe.len())
     }
 
-    fn optimal_recipe_len(&self, target: &str) -> Option<usize> {
-        if target == "e" {
-            return Some(0);
-        }
-        let mut outputs = HashSet::new();
-        outputs.insert(target.to_owned());
-
-        for i in 1.. {
-            println!("{}", i);
-            outputs = outputs
-                .into_iter()
-                .flat_map(|input| {
-                    PossibleTransformations::new(&self.replacements, input, Direction::Reverse)
-                })
-                .scan(false, |recipe_found, (candidate, _)| {
-                    if *recipe_found {
-                        None
-                    } else {
-                        if candidate == "e" {
-                            *recipe_found = true;
-                        }
-                        Some(candidate)
-                    }
-                })
-                .collect();
-            if outputs.is_empty() {
-                return None;
-            } else if outputs.contains("e") {
-                return Some(i);
-            }
-        }
-        None
+    fn optimal_recipe_path(&self, target: String) -> Option<Vec<TransformationInfo>> {
+        RecipeFinder::new(target, &self.replacements).find_shortest_path()
     }
 }
 
-fn print_solution(target: &str, solution: &Vec<TransformationInfo>, replacements: &[Replacement]) {
-    println!("Solution:");
-    println!("  e");
+/// Shows each step of a recipe, starting from a single electron, one molecule per line
+fn format_recipe(
+    target: &str,
+    solution: &[TransformationInfo],
+    replacements: &[Replacement],
+) -> String {
+    let mut lines = vec!["  e".to_owned()];
     let mut current = "e".to_owned();
     for info in solution {
         let replacement = &replacements[info.replacement_index];
-        println!(
+        lines.push(format!(
             "> {}{} => {}",
             " ".repeat(info.input_index),
             replacement.pattern,
             replacement.result,
-        );
+        ));
         current =
-            try_replacement(&current, info.input_index, &replacement, Direction::Forward).unwrap();
-        println!("  {}", current);
+            try_replacement(&current, info.input_index, replacement, Direction::Forward).unwrap();
+        lines.push(format!("  {}", current));
     }
-    println!("= {}", target);
+    lines.push(format!("= {}", target));
+    lines.join("\n")
 }
 
 #[derive(Clone, Copy)]
@@ -292,86 +281,6 @@ impl<'a> Iterator for PossibleTransformations<'a> {
     }
 }
 
-struct Recipes<'a> {
-    replacements: &'a Vec<Replacement>,
-    target: Option<String>,
-    stack: Vec<(PossibleTransformations<'a>, TransformationInfo)>,
-    dead_ends: HashSet<String>,
-}
-
-impl<'a> Recipes<'a> {
-    const ELECTRON: &'static str = "e";
-
-    fn new(replacements: &'a Vec<Replacement>, target: String) -> Self {
-        Self {
-            replacements,
-            target: Some(target),
-            stack: Vec::new(),
-            dead_ends: HashSet::new(),
-        }
-    }
-
-    fn next_candidate(&mut self) -> Option<String> {
-        loop {
-            match self.stack.last_mut() {
-                None => return self.target.take(),
-                Some(&mut (ref mut all_transformations, ref mut current_transformation_info)) => {
-                    if let Some((candidate_molecule, transformation_info)) =
-                        all_transformations.next()
-                    {
-                        *current_transformation_info = transformation_info;
-                        return Some(candidate_molecule);
-                    }
-                }
-            }
-            let (all_transformations, _) = self.stack.pop().unwrap();
-            self.dead_ends.insert(all_transformations.input);
-        }
-    }
-
-    fn recipe_from_stack(&self) -> Vec<TransformationInfo> {
-        self.stack
-            .iter()
-            .rev()
-            .map(|(_, transformation_info)| *transformation_info)
-            .collect()
-    }
-
-    fn push(&mut self, new_target: String) {
-        self.stack.push((
-            self.possible_transformations(new_target),
-            TransformationInfo {
-                replacement_index: 0,
-                input_index: 0,
-            },
-        ))
-    }
-
-    fn next_valid_recipe(&mut self) -> Option<Vec<TransformationInfo>> {
-        while let Some(candidate_molecule) = self.next_candidate() {
-            if candidate_molecule == Self::ELECTRON {
-                let recipe = self.recipe_from_stack();
-                return Some(recipe);
-            } else if !self.dead_ends.contains(&candidate_molecule) {
-                self.push(candidate_molecule);
-            }
-        }
-        None
-    }
-
-    fn possible_transformations(&self, string: String) -> PossibleTransformations<'a> {
-        PossibleTransformations::new(self.replacements, string, Direction::Reverse)
-    }
-}
-
-impl<'a> Iterator for Recipes<'a> {
-    type Item = Vec<TransformationInfo>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.next_valid_recipe()
-    }
-}
-
 #[derive(PartialEq, Eq)]
 struct NodeWithDistanceThrough {
     node: Rc<String>,
@@ -399,6 +308,9 @@ struct RecipeFinder<'a> {
     max_diff_per_step: usize,
     node_distances_to: HashMap<Rc<String>, usize>,
     node_distances_through: HashMap<Rc<String>, usize>,
+    /// The molecule each node was reached from on the shortest path found so far, and the reverse
+    /// transformation used
+    predecessors: HashMap<Rc<String>, (Rc<String>, TransformationInfo)>,
     unvisited: BinaryHeap<NodeWithDistanceThrough>,
 }
 
@@ -407,7 +319,7 @@ impl<'a> RecipeFinder<'a> {
 
     fn new(target: String, replacements: &'a [Replacement]) -> Self {
         let max_diff_per_step = replacements
-            .into_iter()
+            .iter()
             .map(|replacement| replacement.molecule_diff())
             .max()
             .expect("replacement list is empty");
@@ -416,13 +328,14 @@ impl<'a> RecipeFinder<'a> {
             max_diff_per_step,
             node_distances_to: HashMap::new(),
             node_distances_through: HashMap::new(),
+            predecessors: HashMap::new(),
             unvisited: BinaryHeap::new(),
         };
         self_.register_node(target, 0);
         self_
     }
 
-    fn register_node(&mut self, node: String, distance_to: usize) {
+    fn register_node(&mut self, node: String, distance_to: usize) -> Rc<String> {
         let node = Rc::new(node);
         self.node_distances_to.insert(node.clone(), distance_to);
         let distance_through = distance_to + self.estimate_distance_from(&node);
@@ -431,7 +344,8 @@ impl<'a> RecipeFinder<'a> {
         self.unvisited.push(NodeWithDistanceThrough {
             node: node.clone(),
             distance_through,
-        })
+        });
+        node
     }
 
     fn distance_to(&self, node: &String) -> usize {
@@ -448,14 +362,25 @@ impl<'a> RecipeFinder<'a> {
             .unwrap_or(usize::MAX)
     }
 
-    fn estimate_distance_from(&self, node: &String) -> usize {
+    fn estimate_distance_from(&self, node: &str) -> usize {
         let needed_diff = molecule_length(node).saturating_sub(1);
         let remainder = needed_diff % self.max_diff_per_step;
         needed_diff / self.max_diff_per_step + if remainder > 0 { 1 } else { 0 }
     }
 
-    fn find_shortest_path(mut self) -> Option<usize> {
-        let mut max_distance_through = 0;
+    /// Follows the predecessor links from the electron back to the target. As every step of the
+    /// search undid a replacement, the transformations come out in forward order
+    fn recipe_to(&self, node: &String) -> Vec<TransformationInfo> {
+        let mut recipe = Vec::new();
+        let mut current = node;
+        while let Some((predecessor, info)) = self.predecessors.get(current) {
+            recipe.push(*info);
+            current = predecessor;
+        }
+        recipe
+    }
+
+    fn find_shortest_path(mut self) -> Option<Vec<TransformationInfo>> {
         while let Some(NodeWithDistanceThrough {
             node: current,
             distance_through: current_distance_through,
@@ -463,16 +388,8 @@ impl<'a> RecipeFinder<'a> {
         {
             let current_distance = self.distance_to(&current);
 
-            if current_distance_through > max_distance_through {
-                max_distance_through = current_distance_through;
-                println!(
-                    "distance_to: {:4}, distance_through: {:4} - {}",
-                    current_distance, current_distance_through, current
-                );
-            }
-
             if *current == Self::ELECTRON {
-                return Some(current_distance);
+                return Some(self.recipe_to(&current));
             }
 
             if self.distance_through(&current) < current_distance_through {
@@ -480,15 +397,16 @@ impl<'a> RecipeFinder<'a> {
             }
 
             let neighbor_distance = current_distance + 1;
-            for (neighbor, _) in PossibleTransformations::new(
-                &self.replacements,
+            for (neighbor, info) in PossibleTransformations::new(
+                self.replacements,
                 (*current).clone(),
                 Direction::Reverse,
             )
             .unique_molecules()
             {
                 if neighbor_distance < self.distance_to(&neighbor) {
-                    self.register_node(neighbor, neighbor_distance)
+                    let neighbor = self.register_node(neighbor, neighbor_distance);
+                    self.predecessors.insert(neighbor, (current.clone(), info));
                 }
             }
         }
@@ -511,6 +429,16 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_input_parsing() {
+        let day = Day19::new("H => HO\r\nO => HH\r\n\r\nHOH\r\n").unwrap();
+        assert_eq!(day.replacements.len(), 2);
+        assert_eq!(day.replacements[1].result, "HH");
+        assert_eq!(day.input_molecule, "HOH");
+        assert!(Day19::new("H => HO\nHOH\n").is_err());
+        assert!(Day19::new("\nHOH\n").is_err());
+    }
+
     #[test]
     fn test_machine_calibration() {
         let machine = Machine::with_replacements(
@@ -538,25 +466,29 @@ mod tests {
                 .map(|line| line.parse::<Replacement>().unwrap()),
         );
 
-        assert_eq!(machine.optimal_recipe("".to_owned()), None);
-        assert_eq!(machine.optimal_recipe("e".to_owned()).unwrap().len(), 0);
-        assert_eq!(machine.optimal_recipe("H".to_owned()).unwrap().len(), 1);
-        assert_eq!(machine.optimal_recipe("HOH".to_owned()).unwrap().len(), 3);
-        assert_eq!(
-            machine.optimal_recipe("HOHOHO".to_owned()).unwrap().len(),
-            6
-        );
-        assert_eq!(machine.optimal_recipe_len(""), None);
-        assert_eq!(machine.optimal_recipe_len("e").unwrap(), 0);
-        assert_eq!(machine.optimal_recipe_len("H").unwrap(), 1);
-        assert_eq!(machine.optimal_recipe_len("HOH").unwrap(), 3);
-        assert_eq!(machine.optimal_recipe_len("HOHOHO").unwrap(), 6);
-
         assert_eq!(machine.optimal_recipe_a_star("".to_owned()), None);
         assert_eq!(machine.optimal_recipe_a_star("e".to_owned()).unwrap(), 0);
         assert_eq!(machine.optimal_recipe_a_star("H".to_owned()).unwrap(), 1);
         assert_eq!(machine.optimal_recipe_a_star("HOH".to_owned()).unwrap(), 3);
-        assert_eq!(machine.optimal_recipe_a_star("HOHOHO".to_owned()).unwrap(), 6);
+        assert_eq!(
+            machine.optimal_recipe_a_star("HOHOHO".to_owned()).unwrap(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_format_recipe() {
+        let replacements: Vec<Replacement> = ["e => H", "e => O", "H => HO", "H => OH", "O => HH"]
+            .into_iter()
+            .map(|line| line.parse().unwrap())
+            .collect();
+        let machine = Machine::with_replacements(replacements.clone());
+
+        let recipe = machine.optimal_recipe_path("HOH".to_owned()).unwrap();
+        let formatted = format_recipe("HOH", &recipe, &replacements);
+        assert_eq!(formatted.lines().next(), Some("  e"));
+        assert_eq!(formatted.lines().nth(recipe.len() * 2), Some("  HOH"));
+        assert_eq!(formatted.lines().last(), Some("= HOH"));
     }
 
     #[test]
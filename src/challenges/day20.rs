@@ -1,6 +1,4 @@
-use std::time::Instant;
-
-use super::Challenge;
+use super::{Challenge, ChallengeError, ChallengeResult};
 
 pub struct Day20 {
     input: u32,
@@ -12,29 +10,23 @@ impl Challenge for Day20 {
     type Part1Solution = u32;
     type Part2Solution = u32;
 
-    fn new(input: &str) -> Self {
-        Self {
-            input: input.trim().parse::<u32>().unwrap(),
-        }
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Ok(Self {
+            input: input
+                .trim()
+                .parse::<u32>()
+                .map_err(|error| ChallengeError::at_line(1, error))?,
+        })
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
-        let start = Instant::now();
-        let mut progress = 1u32;
-        for (house, num_presents) in PresentsUsingPrimes::new() {
-            if num_presents >= self.input {
-                println!("took {:?}", start.elapsed());
-                return house;
-            }
-            if num_presents >= self.input * progress / 100 {
-                println!("{:3}: {}", progress, house);
-                progress += 1;
-            }
-        }
-        0
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        PresentsUsingPrimes::new()
+            .find(|&(_, num_presents)| num_presents >= self.input)
+            .map(|(house, _)| house)
+            .ok_or_else(|| ChallengeError::new("ran out of houses"))
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
         let stamina = 50;
         let multiplier = 11;
         let min_house = (self.input as f64
@@ -43,19 +35,13 @@ impl Challenge for Day20 {
         for house in min_house.. {
             let num_presents = presents_stamina(house, stamina, 11);
             if num_presents >= self.input {
-                return house;
+                return Ok(house);
             }
         }
-        0
+        Err(ChallengeError::new("ran out of houses"))
     }
 }
 
-fn presents_naive(house: u32) -> u32 {
-    (1..=house)
-        .map(|i| if house % i == 0 { i * 10 } else { 0 })
-        .sum()
-}
-
 struct PresentsUsingPrimes {
     house: u32,
     primes: Vec<u32>,
@@ -74,7 +60,7 @@ impl PresentsUsingPrimes {
         let mut x = self.house;
         for prime in self.primes.iter().copied() {
             let mut power = 0;
-            while x % prime == 0 {
+            while x.is_multiple_of(prime) {
                 x /= prime;
                 power += 1;
             }
@@ -82,7 +68,7 @@ impl PresentsUsingPrimes {
                 prime_factors.push((prime, power))
             }
         }
-        if prime_factors.len() == 0 {
+        if prime_factors.is_empty() {
             if x == 1 {
                 // 1 is a special case
                 prime_factors.push((2, 0));
@@ -98,7 +84,7 @@ impl PresentsUsingPrimes {
     fn all_divisors_from_prime_factors<'a>(
         &'a self,
         prime_factors: &'a Vec<(u32, u32)>,
-    ) -> AllDivisorsFromPrimeFactors {
+    ) -> AllDivisorsFromPrimeFactors<'a> {
         AllDivisorsFromPrimeFactors::new(prime_factors)
     }
 
@@ -133,7 +119,7 @@ impl<'a> AllDivisorsFromPrimeFactors<'a> {
     }
 
     fn current_factor(&self) -> Option<u32> {
-        if self.current_powers.len() == 0 {
+        if self.current_powers.is_empty() {
             None
         } else {
             Some(
@@ -175,7 +161,7 @@ impl<'a> Iterator for AllDivisorsFromPrimeFactors<'a> {
 fn presents_stamina(house: u32, stamina: u32, multiplier: u32) -> u32 {
     (1..=stamina)
         .filter_map(|divisor| {
-            if house % divisor == 0 {
+            if house.is_multiple_of(divisor) {
                 Some(house / divisor)
             } else {
                 None
@@ -189,6 +175,13 @@ fn presents_stamina(house: u32, stamina: u32, multiplier: u32) -> u32 {
 mod tests {
     use super::*;
 
+    /// The presents at `house` by trying every elf, to check the faster ways against
+    fn presents_naive(house: u32) -> u32 {
+        (1..=house)
+            .map(|i| if house.is_multiple_of(i) { i * 10 } else { 0 })
+            .sum()
+    }
+
     #[test]
     fn test_naive() {
         assert_eq!(presents_naive(1), 10);
@@ -2,13 +2,13 @@ use std::{cmp::max, collections::HashMap, ops::Range, str::FromStr};
 
 use itertools::{Itertools, Product};
 
-use super::Challenge;
+use super::{Challenge, ChallengeError, ChallengeResult, Explain};
 
 pub struct Day21 {
     boss: Boss,
 }
 
-fn inventory_cost(inventory: &Vec<Item>) -> u32 {
+fn inventory_cost(inventory: &[Item]) -> u32 {
     inventory.iter().map(|item| item.cost).sum()
 }
 
@@ -17,6 +17,32 @@ impl Day21 {
         let player = Player::with_inventory(100, inventory);
         Battle::new(&player, &self.boss).resolve()
     }
+
+    fn cheapest_winning_inventory(&self) -> ChallengeResult<Vec<Item>> {
+        AllPossibleInventories::new(ITEMS)
+            .filter(|inventory| self.winner_given_inventory(inventory.clone()) == Winner::Player)
+            .min_by_key(|inventory| inventory_cost(inventory))
+            .ok_or_else(|| ChallengeError::new("no inventory wins against the boss"))
+    }
+
+    fn most_expensive_losing_inventory(&self) -> ChallengeResult<Vec<Item>> {
+        AllPossibleInventories::new(ITEMS)
+            .filter(|inventory| self.winner_given_inventory(inventory.clone()) == Winner::Boss)
+            .max_by_key(|inventory| inventory_cost(inventory))
+            .ok_or_else(|| ChallengeError::new("no inventory loses against the boss"))
+    }
+}
+
+fn describe_inventory(inventory: Vec<Item>) -> String {
+    let names = inventory.iter().map(|item| item.name).join(", ");
+    let player = Player::with_inventory(100, inventory);
+    format!(
+        "{} (cost {}, damage {}, armor {})",
+        names,
+        player.inventory_cost(),
+        player.damage(),
+        player.armor()
+    )
 }
 
 impl Challenge for Day21 {
@@ -25,38 +51,48 @@ impl Challenge for Day21 {
     type Part1Solution = u32;
     type Part2Solution = u32;
 
-    fn new(input: &str) -> Self {
-        Self {
-            boss: input.parse::<Boss>().unwrap(),
-        }
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Ok(Self {
+            boss: input.parse::<Boss>().map_err(ChallengeError::new)?,
+        })
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
-        AllPossibleInventories::new(ITEMS)
-            .filter(|inventory| self.winner_given_inventory(inventory.clone()) == Winner::Player)
-            .map(|inventory| inventory_cost(&inventory))
-            .min()
-            .expect("no valid inventory")
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        Ok(inventory_cost(&self.cheapest_winning_inventory()?))
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
-        AllPossibleInventories::new(ITEMS)
-            .filter(|inventory| self.winner_given_inventory(inventory.clone()) == Winner::Boss)
-            .map(|inventory| inventory_cost(&inventory))
-            .max()
-            .expect("no valid inventory")
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        Ok(inventory_cost(&self.most_expensive_losing_inventory()?))
+    }
+}
+
+impl Explain for Day21 {
+    fn explain_part1(&self) -> ChallengeResult<(u32, String)> {
+        let inventory = self.cheapest_winning_inventory()?;
+        Ok((
+            inventory_cost(&inventory),
+            format!("cheapest win: {}", describe_inventory(inventory)),
+        ))
+    }
+
+    fn explain_part2(&self) -> ChallengeResult<(u32, String)> {
+        let inventory = self.most_expensive_losing_inventory()?;
+        Ok((
+            inventory_cost(&inventory),
+            format!("most expensive loss: {}", describe_inventory(inventory)),
+        ))
     }
 }
 
 #[derive(Debug, Clone, Copy)]
-enum ItemKind {
+pub enum ItemKind {
     Weapon,
     Armor,
     Ring,
 }
 
 #[derive(Debug, Clone, Copy)]
-struct Item {
+pub struct Item {
     kind: ItemKind,
     name: &'static str,
     cost: u32,
@@ -65,7 +101,13 @@ struct Item {
 }
 
 impl Item {
-    const fn new(kind: ItemKind, name: &'static str, cost: u32, damage: u32, armor: u32) -> Self {
+    pub const fn new(
+        kind: ItemKind,
+        name: &'static str,
+        cost: u32,
+        damage: u32,
+        armor: u32,
+    ) -> Self {
         Self {
             kind,
             name,
@@ -76,7 +118,8 @@ impl Item {
     }
 }
 
-static ITEMS: &[Item] = &[
+/// Everything the shop sells
+pub static ITEMS: &[Item] = &[
     Item::new(ItemKind::Weapon, "Dagger", 8, 4, 0),
     Item::new(ItemKind::Weapon, "Shortsword", 10, 5, 0),
     Item::new(ItemKind::Weapon, "Warhammer", 25, 6, 0),
@@ -95,29 +138,29 @@ static ITEMS: &[Item] = &[
     Item::new(ItemKind::Ring, "Defense +3", 80, 0, 3),
 ];
 
-struct Player {
+pub struct Player {
     hit_points: u32,
     inventory: Vec<Item>,
 }
 
 impl Player {
-    fn new(hit_points: u32) -> Self {
+    pub fn new(hit_points: u32) -> Self {
         Self {
             hit_points,
             inventory: Vec::new(),
         }
     }
-    fn with_inventory(hit_points: u32, inventory: Vec<Item>) -> Self {
+    pub fn with_inventory(hit_points: u32, inventory: Vec<Item>) -> Self {
         let mut self_ = Self::new(hit_points);
         self_.set_inventory(inventory);
         self_
     }
 
-    fn set_inventory(&mut self, inventory: Vec<Item>) {
+    pub fn set_inventory(&mut self, inventory: Vec<Item>) {
         self.inventory = inventory;
     }
 
-    fn inventory_cost(&self) -> u32 {
+    pub fn inventory_cost(&self) -> u32 {
         self.inventory.iter().map(|item| item.cost).sum()
     }
 }
@@ -137,7 +180,7 @@ impl Fighter for Player {
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct Boss {
+pub struct Boss {
     hit_points: u32,
     damage: u32,
     armor: u32,
@@ -193,19 +236,20 @@ impl FromStr for Boss {
     }
 }
 
-trait Fighter {
+pub trait Fighter {
     fn hit_points(&self) -> u32;
     fn damage(&self) -> u32;
     fn armor(&self) -> u32;
 }
 
 #[derive(Debug, PartialEq, Eq)]
-enum Winner {
+pub enum Winner {
     Player,
     Boss,
 }
 
-struct Battle<'a> {
+/// A fight between the player and the boss, the player attacks first
+pub struct Battle<'a> {
     player: &'a Player,
     boss: &'a Boss,
     player_hit_points: u32,
@@ -214,7 +258,7 @@ struct Battle<'a> {
 }
 
 impl<'a> Battle<'a> {
-    fn new(player: &'a Player, boss: &'a Boss) -> Self {
+    pub fn new(player: &'a Player, boss: &'a Boss) -> Self {
         Self {
             player,
             boss,
@@ -224,11 +268,11 @@ impl<'a> Battle<'a> {
         }
     }
 
-    fn player_hit_points(&self) -> u32 {
+    pub fn player_hit_points(&self) -> u32 {
         self.player_hit_points
     }
 
-    fn boss_hit_points(&self) -> u32 {
+    pub fn boss_hit_points(&self) -> u32 {
         self.boss_hit_points
     }
 
@@ -257,7 +301,7 @@ impl<'a> Battle<'a> {
         *defender_hit_points = defender_hit_points.saturating_sub(damage);
     }
 
-    fn next_turn(&mut self) {
+    pub fn next_turn(&mut self) {
         self.deal_damage_to_defender(Self::calculate_damage(self.attacker(), self.defender()));
         self.player_turn = !self.player_turn;
     }
@@ -266,7 +310,7 @@ impl<'a> Battle<'a> {
         max(attacker.damage().saturating_sub(defender.armor()), 1)
     }
 
-    fn winner(&self) -> Option<Winner> {
+    pub fn winner(&self) -> Option<Winner> {
         if self.boss_hit_points == 0 {
             Some(Winner::Player)
         } else if self.player_hit_points == 0 {
@@ -276,7 +320,7 @@ impl<'a> Battle<'a> {
         }
     }
 
-    fn resolve(&mut self) -> Winner {
+    pub fn resolve(&mut self) -> Winner {
         loop {
             self.next_turn();
             if let Some(winner) = self.winner() {
@@ -1,14 +1,35 @@
 use std::{
-    cmp::max,
+    cmp::{max, Ordering},
     collections::{BinaryHeap, HashMap},
-    fmt::Display,
+    fmt::{Display, Write},
+    fs,
+    hash::{Hash, Hasher},
+    path::Path,
     str::FromStr,
 };
 
-use super::Challenge;
+use serde_json::Value;
+
+use super::{Challenge, ChallengeError, ChallengeResult, Explain};
+use crate::params::{day_params, Params};
 
 pub struct Day22 {
     boss: Boss,
+    params: Day22Params,
+}
+
+day_params! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Day22Params {
+        /// The player's hit points at the start of the fight
+        hit_points: u32 = 50,
+        /// The player's mana at the start of the fight
+        mana: u32 = 500,
+        /// The spells the player can cast, overridden by the path of a spell book file
+        spell_book: SpellBook = SpellBook::default() => |path: &String| {
+            SpellBook::load(Path::new(path)).map_err(ChallengeError::new)
+        },
+    }
 }
 
 impl Challenge for Day22 {
@@ -17,29 +38,77 @@ impl Challenge for Day22 {
     type Part1Solution = u32;
     type Part2Solution = u32;
 
-    fn new(input: &str) -> Self {
-        Self {
-            boss: input.parse::<Boss>().unwrap(),
-        }
+    const PARAMS: &'static [&'static str] = Day22Params::NAMES;
+
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Self::with_params(input, &Params::default())
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
-        let initial_state = Game::new(Player::new(50, 500), self.boss, Difficulty::Normal);
-        DijkstraOptimizer::new(initial_state)
+    fn with_params(input: &str, params: &Params) -> ChallengeResult<Self> {
+        Ok(Self {
+            boss: input.parse::<Boss>().map_err(ChallengeError::new)?,
+            params: Day22Params::from_params(params)?,
+        })
+    }
+
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        DijkstraOptimizer::new(self.initial_state(Difficulty::Normal))
             .find_lowest_mana_cost_to_win()
-            .unwrap()
+            .map_err(ChallengeError::new)
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
-        let initial_state = Game::new(Player::new(50, 500), self.boss, Difficulty::Hard);
-        DijkstraOptimizer::new(initial_state)
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        DijkstraOptimizer::new(self.initial_state(Difficulty::Hard))
             .find_lowest_mana_cost_to_win()
-            .unwrap()
+            .map_err(ChallengeError::new)
+    }
+}
+
+impl Explain for Day22 {
+    fn explain_part1(&self) -> ChallengeResult<(u32, String)> {
+        self.explain_cheapest_win(Difficulty::Normal)
+    }
+
+    fn explain_part2(&self) -> ChallengeResult<(u32, String)> {
+        self.explain_cheapest_win(Difficulty::Hard)
+    }
+}
+
+impl Day22 {
+    /// The game at the start of the fight, for the player's hit points, mana and spell book from
+    /// the params
+    pub fn initial_state(&self, difficulty: Difficulty) -> Game<'_> {
+        let player = Player::new(self.params.hit_points, self.params.mana);
+        Game::new(&self.params.spell_book, player, self.boss, difficulty)
+    }
+
+    /// Lists every cheapest spell sequence, followed by the battle log of the first one
+    fn explain_cheapest_win(&self, difficulty: Difficulty) -> ChallengeResult<(u32, String)> {
+        let initial_state = self.initial_state(difficulty);
+        let wins = DijkstraOptimizer::new(initial_state)
+            .find_all_cheapest_wins(usize::MAX)
+            .map_err(ChallengeError::new)?;
+        let mut explanation = String::new();
+        for spells in &wins.sequences {
+            writeln!(
+                explanation,
+                "casting {} spends {} mana",
+                self.params.spell_book.names(spells),
+                wins.mana_cost
+            )
+            .unwrap();
+        }
+        let battle_log = initial_state
+            .battle_log(&wins.sequences[0])
+            .map_err(ChallengeError::new)?;
+        explanation.push('\n');
+        explanation.push_str(&battle_log);
+        Ok((wins.mana_cost, explanation))
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Player {
+pub struct Player {
     hit_points: u32,
     armor: u32,
     mana: u32,
@@ -53,6 +122,18 @@ impl Player {
             mana,
         }
     }
+
+    pub fn hit_points(&self) -> u32 {
+        self.hit_points
+    }
+
+    pub fn armor(&self) -> u32 {
+        self.armor
+    }
+
+    pub fn mana(&self) -> u32 {
+        self.mana
+    }
 }
 
 impl Display for Player {
@@ -69,7 +150,7 @@ impl Display for Player {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Boss {
+pub struct Boss {
     hit_points: u32,
     damage: u32,
 }
@@ -78,6 +159,14 @@ impl Boss {
     pub fn new(hit_points: u32, damage: u32) -> Self {
         Self { hit_points, damage }
     }
+
+    pub fn hit_points(&self) -> u32 {
+        self.hit_points
+    }
+
+    pub fn damage(&self) -> u32 {
+        self.damage
+    }
 }
 
 impl Display for Boss {
@@ -86,106 +175,298 @@ impl Display for Boss {
     }
 }
 
-#[derive(Clone, Copy)]
-enum Spell {
-    MagicMissile,
-    Drain,
-    Shield,
-    Poison,
-    Recharge,
-}
+/// A spell, identified by its position in the [`SpellBook`] it's cast from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Spell(u8);
 
 impl Spell {
-    fn mana_cost(&self) -> u32 {
-        match self {
-            Self::MagicMissile => 53,
-            Self::Drain => 73,
-            Self::Shield => 113,
-            Self::Poison => 173,
-            Self::Recharge => 229,
-        }
+    // the spells of the puzzle's spell book, see `SpellBook::default`
+    pub const MAGIC_MISSILE: Spell = Spell(0);
+    pub const DRAIN: Spell = Spell(1);
+    pub const SHIELD: Spell = Spell(2);
+    pub const POISON: Spell = Spell(3);
+    pub const RECHARGE: Spell = Spell(4);
+
+    pub fn index(&self) -> usize {
+        self.0 as usize
     }
+}
 
-    fn cast(&self, game: &mut Game) {
-        game.player.mana -= self.mana_cost();
-        match self {
-            Spell::MagicMissile => deal_damage(&mut game.boss.hit_points, 4),
-            Spell::Drain => {
-                deal_damage(&mut game.boss.hit_points, 2);
-                game.player.hit_points += 2;
+/// What a spell does when it's cast, or what its timed effect does at the start of every turn
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Impact {
+    /// Damage dealt to the boss
+    pub damage: u32,
+    /// Hit points the player regains
+    pub heal: u32,
+    /// Mana the player regains
+    pub mana: u32,
+}
+
+/// An effect that lasts for some turns after its spell was cast
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TimedEffect {
+    /// How many turns the effect lasts, counting both the player's and the boss's turns
+    pub turns: u8,
+    /// Extra armor for the player while the effect is active
+    pub armor: u32,
+    pub each_turn: Impact,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpellDefinition {
+    pub name: String,
+    pub mana_cost: u32,
+    pub instant: Impact,
+    /// A spell can't be cast again while its effect is active
+    pub effect: Option<TimedEffect>,
+}
+
+/// The spells the player can choose from. Loaded from a JSON file of the form
+/// `[{"name": "Drain", "cost": 73, "damage": 2, "heal": 2},
+/// {"name": "Poison", "cost": 173, "effect": {"turns": 6, "damage": 3}}]`, where `damage`, `heal`
+/// and `mana` are optional and an effect may also give `armor`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpellBook {
+    spells: Vec<SpellDefinition>,
+}
+
+impl SpellBook {
+    /// Bounds the size of [`EffectTimers`], which is part of every game state
+    pub const MAX_SPELLS: usize = 16;
+
+    pub fn new(spells: Vec<SpellDefinition>) -> Result<Self, ParseError> {
+        if spells.is_empty() || spells.len() > Self::MAX_SPELLS {
+            return Err(format!(
+                "expected 1 to {} spells, got {}",
+                Self::MAX_SPELLS,
+                spells.len()
+            ));
+        }
+        for (index, spell) in spells.iter().enumerate() {
+            if spells[..index]
+                .iter()
+                .any(|other| other.name.eq_ignore_ascii_case(&spell.name))
+            {
+                return Err(format!("{}: defined twice", spell.name));
+            }
+            if spell.mana_cost == 0 {
+                // a free spell could be cast forever without getting any closer to a win
+                return Err(format!("{}: must cost at least 1 mana", spell.name));
+            }
+            if spell.effect.is_some_and(|effect| effect.turns == 0) {
+                return Err(format!(
+                    "{}: the effect must last at least 1 turn",
+                    spell.name
+                ));
             }
-            Self::Shield => game.activate_effect(Effect::Shield, 6),
-            Spell::Poison => game.activate_effect(Effect::Poison, 6),
-            Spell::Recharge => game.activate_effect(Effect::Recharge, 5),
         }
+        Ok(Self { spells })
     }
 
-    fn effect(&self) -> Option<Effect> {
-        match self {
-            Spell::MagicMissile | Spell::Drain => None,
-            Spell::Shield => Some(Effect::Shield),
-            Spell::Poison => Some(Effect::Poison),
-            Spell::Recharge => Some(Effect::Recharge),
-        }
+    pub fn load(path: &Path) -> Result<Self, ParseError> {
+        let contents = fs::read_to_string(path)
+            .map_err(|error| format!("could not read {}: {}", path.display(), error))?;
+        contents
+            .parse()
+            .map_err(|error| format!("invalid spell book {}: {}", path.display(), error))
+    }
+
+    pub fn spells(&self) -> impl Iterator<Item = Spell> {
+        (0..self.spells.len()).map(|index| Spell(index as u8))
+    }
+
+    pub fn get(&self, spell: Spell) -> Option<&SpellDefinition> {
+        self.spells.get(spell.index())
+    }
+
+    /// Panics if `spell` isn't in this book
+    pub fn definition(&self, spell: Spell) -> &SpellDefinition {
+        &self.spells[spell.index()]
+    }
+
+    pub fn name(&self, spell: Spell) -> &str {
+        &self.definition(spell).name
+    }
+
+    /// The spell called `name`, ignoring case
+    pub fn find(&self, name: &str) -> Option<Spell> {
+        self.spells()
+            .find(|&spell| self.name(spell).eq_ignore_ascii_case(name))
+    }
+
+    /// The names of `spells`, separated by commas
+    pub fn names(&self, spells: &[Spell]) -> String {
+        spells
+            .iter()
+            .map(|&spell| self.name(spell))
+            .collect::<Vec<_>>()
+            .join(", ")
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Effect {
-    Shield,
-    Poison,
-    Recharge,
+/// The spells from the puzzle description
+impl Default for SpellBook {
+    fn default() -> Self {
+        let spell = |name: &str, mana_cost, instant, effect| SpellDefinition {
+            name: name.to_owned(),
+            mana_cost,
+            instant,
+            effect,
+        };
+        let damage = |damage| Impact {
+            damage,
+            ..Impact::default()
+        };
+        Self::new(vec![
+            spell("Magic Missile", 53, damage(4), None),
+            spell(
+                "Drain",
+                73,
+                Impact {
+                    damage: 2,
+                    heal: 2,
+                    ..Impact::default()
+                },
+                None,
+            ),
+            spell(
+                "Shield",
+                113,
+                Impact::default(),
+                Some(TimedEffect {
+                    turns: 6,
+                    armor: 7,
+                    each_turn: Impact::default(),
+                }),
+            ),
+            spell(
+                "Poison",
+                173,
+                Impact::default(),
+                Some(TimedEffect {
+                    turns: 6,
+                    armor: 0,
+                    each_turn: damage(3),
+                }),
+            ),
+            spell(
+                "Recharge",
+                229,
+                Impact::default(),
+                Some(TimedEffect {
+                    turns: 5,
+                    armor: 0,
+                    each_turn: Impact {
+                        mana: 101,
+                        ..Impact::default()
+                    },
+                }),
+            ),
+        ])
+        .expect("the puzzle's spell book is valid")
+    }
 }
 
-impl Effect {
-    pub fn activate(&self, player: &mut Player, _boss: &mut Boss) {
-        match self {
-            Effect::Shield => player.armor += 7,
-            Effect::Poison | Effect::Recharge => (),
-        }
+impl FromStr for SpellBook {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value: Value = serde_json::from_str(s).map_err(|error| error.to_string())?;
+        let spells = value
+            .as_array()
+            .ok_or("expected an array of spells")?
+            .iter()
+            .map(parse_spell_definition)
+            .collect::<Result<_, _>>()?;
+        Self::new(spells)
     }
-    pub fn apply(&self, player: &mut Player, boss: &mut Boss) {
-        match self {
-            Effect::Shield => (),
-            Effect::Poison => deal_damage(&mut boss.hit_points, 3),
-            Effect::Recharge => player.mana += 101,
-        }
+}
+
+fn parse_spell_definition(value: &Value) -> Result<SpellDefinition, ParseError> {
+    let fields = value.as_object().ok_or("expected an object per spell")?;
+    let name = fields
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or("expected a name for every spell")?;
+    let mut spell = SpellDefinition {
+        name: name.to_owned(),
+        mana_cost: 0,
+        instant: Impact::default(),
+        effect: None,
+    };
+    for (key, value) in fields {
+        let result = match key.as_str() {
+            "name" => Ok(()),
+            "cost" => parse_json_number(key, value).map(|cost| spell.mana_cost = cost),
+            "effect" => parse_timed_effect(value).map(|effect| spell.effect = Some(effect)),
+            _ => parse_impact_field(&mut spell.instant, key, value),
+        };
+        result.map_err(|error| format!("{}: {}", name, error))?;
     }
+    if !fields.contains_key("cost") {
+        return Err(format!("{}: expected a cost", name));
+    }
+    Ok(spell)
+}
 
-    pub fn deactivate(&self, player: &mut Player, _boss: &mut Boss) {
-        match self {
-            Effect::Shield => player.armor -= 7,
-            Effect::Poison | Effect::Recharge => (),
+fn parse_timed_effect(value: &Value) -> Result<TimedEffect, ParseError> {
+    let fields = value
+        .as_object()
+        .ok_or("expected an object for the effect")?;
+    let mut effect = TimedEffect::default();
+    for (key, value) in fields {
+        match key.as_str() {
+            "turns" => effect.turns = parse_json_number(key, value)?,
+            "armor" => effect.armor = parse_json_number(key, value)?,
+            _ => parse_impact_field(&mut effect.each_turn, key, value)?,
         }
     }
+    if !fields.contains_key("turns") {
+        return Err("expected the number of turns the effect lasts".to_owned());
+    }
+    Ok(effect)
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
-struct EffectTimers {
-    shield_timer: u8,
-    poison_timer: u8,
-    recharge_timer: u8,
+fn parse_impact_field(impact: &mut Impact, key: &str, value: &Value) -> Result<(), ParseError> {
+    let field = match key {
+        "damage" => &mut impact.damage,
+        "heal" => &mut impact.heal,
+        "mana" => &mut impact.mana,
+        _ => return Err(format!("unknown field {:?}", key)),
+    };
+    *field = parse_json_number(key, value)?;
+    Ok(())
+}
+
+fn parse_json_number<T: TryFrom<u64>>(key: &str, value: &Value) -> Result<T, ParseError> {
+    value
+        .as_u64()
+        .and_then(|number| T::try_from(number).ok())
+        .ok_or_else(|| format!("{}: expected a non-negative number in range", key))
+}
+
+/// The turns left on the effect of each spell, 0 if it isn't active
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct EffectTimers {
+    timers: [u8; SpellBook::MAX_SPELLS],
 }
 
 impl EffectTimers {
     pub fn new() -> Self {
-        Self {
-            shield_timer: 0,
-            poison_timer: 0,
-            recharge_timer: 0,
-        }
+        Self::default()
     }
 
-    pub fn is_active(&self, effect: Effect) -> bool {
-        self.timer(effect) > 0
+    pub fn is_active(&self, spell: Spell) -> bool {
+        self.timer(spell) > 0
     }
 
-    pub fn activate(&mut self, effect: Effect, duration: u8) {
-        *self.timer_mut(effect) = duration;
+    pub fn activate(&mut self, spell: Spell, turns: u8) {
+        self.timers[spell.index()] = turns;
     }
 
-    pub fn try_decrement(&mut self, effect: Effect) -> Result<u8, ()> {
-        let timer = self.timer_mut(effect);
+    fn try_decrement(&mut self, spell: Spell) -> Result<u8, ()> {
+        let timer = &mut self.timers[spell.index()];
         if *timer == 0 {
             Err(())
         } else {
@@ -194,26 +475,28 @@ impl EffectTimers {
         }
     }
 
-    fn timer(&self, effect: Effect) -> u8 {
-        match effect {
-            Effect::Shield => self.shield_timer,
-            Effect::Poison => self.poison_timer,
-            Effect::Recharge => self.recharge_timer,
-        }
+    pub fn timer(&self, spell: Spell) -> u8 {
+        self.timers[spell.index()]
     }
+}
 
-    fn timer_mut(&mut self, effect: Effect) -> &mut u8 {
-        match effect {
-            Effect::Shield => &mut self.shield_timer,
-            Effect::Poison => &mut self.poison_timer,
-            Effect::Recharge => &mut self.recharge_timer,
-        }
+/// The state of both fighters and the active effects, one line each
+impl Display for Game<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "- {}\n- {}\n- {}",
+            self.player,
+            self.boss,
+            self.active_effects()
+        )
     }
 }
 
 #[derive(Debug, PartialEq, Eq)]
-enum GameError {
+pub enum GameError {
     GameFinished,
+    UnknownSpell,
     NotEnoughMana,
     EffectAlreadyActive,
 }
@@ -225,6 +508,7 @@ impl Display for GameError {
             "{}",
             match self {
                 Self::GameFinished => "game is already finished",
+                Self::UnknownSpell => "spell isn't in the spell book",
                 Self::NotEnoughMana => "not enough mana",
                 Self::EffectAlreadyActive => "effect already active",
             }
@@ -234,23 +518,129 @@ impl Display for GameError {
 
 impl std::error::Error for GameError {}
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
-enum Difficulty {
+/// Something that happens during a turn, see [`Game::battle_log`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnEvent {
+    /// The player lost a hit point at the start of their turn
+    HardDifficulty,
+    /// The effect of `spell` had its `impact` and its timer went down to `timer`
+    EffectTicked {
+        spell: Spell,
+        impact: Impact,
+        timer: u8,
+    },
+    /// The effect of `spell` ended, taking away the `armor` it gave
+    EffectWoreOff {
+        spell: Spell,
+        armor: u32,
+    },
+    Cast(Spell),
+    /// The boss attacked with `damage`, against the player's `armor`
+    BossAttack {
+        damage: u32,
+        armor: u32,
+    },
+}
+
+impl TurnEvent {
+    /// A line of the battle log, e.g. `Poison deals 3 damage; its timer is now 5.`
+    pub fn describe(self, spell_book: &SpellBook) -> String {
+        match self {
+            TurnEvent::HardDifficulty => "Player loses 1 hit point.".to_owned(),
+            TurnEvent::EffectTicked {
+                spell,
+                impact,
+                timer,
+            } => {
+                let name = spell_book.name(spell);
+                let effects = describe_impact(impact, ["deals", "heals", "provides"]);
+                if effects.is_empty() {
+                    format!("{}'s timer is now {}.", name, timer)
+                } else {
+                    format!("{} {}; its timer is now {}.", name, effects, timer)
+                }
+            }
+            TurnEvent::EffectWoreOff { spell, armor } if armor > 0 => format!(
+                "{} wears off, decreasing armor by {}.",
+                spell_book.name(spell),
+                armor
+            ),
+            TurnEvent::EffectWoreOff { spell, .. } => {
+                format!("{} wears off.", spell_book.name(spell))
+            }
+            TurnEvent::Cast(spell) => {
+                let definition = spell_book.definition(spell);
+                let mut effects =
+                    describe_impact(definition.instant, ["dealing", "healing", "providing"]);
+                if let Some(effect) = definition.effect.filter(|effect| effect.armor > 0) {
+                    if !effects.is_empty() {
+                        effects.push_str(", ");
+                    }
+                    write!(effects, "increasing armor by {}", effect.armor).unwrap();
+                }
+                if effects.is_empty() {
+                    format!("Player casts {}.", definition.name)
+                } else {
+                    format!("Player casts {}, {}.", definition.name, effects)
+                }
+            }
+            TurnEvent::BossAttack { damage, armor: 0 } => {
+                format!("Boss attacks for {} damage.", damage)
+            }
+            TurnEvent::BossAttack { damage, armor } => format!(
+                "Boss attacks for {} - {} = {} damage.",
+                damage,
+                armor,
+                max(damage.saturating_sub(armor), 1)
+            ),
+        }
+    }
+}
+
+/// The non-zero parts of `impact`, e.g. `deals 2 damage, heals 2 hit points`, with the verbs for
+/// damage, healing and mana in that order
+fn describe_impact(impact: Impact, verbs: [&str; 3]) -> String {
+    let [damage, heal, mana] = verbs;
+    let mut parts = Vec::new();
+    if impact.damage > 0 {
+        parts.push(format!("{} {} damage", damage, impact.damage));
+    }
+    if impact.heal > 0 {
+        let s = if impact.heal > 1 { "s" } else { "" };
+        parts.push(format!("{} {} hit point{}", heal, impact.heal, s));
+    }
+    if impact.mana > 0 {
+        parts.push(format!("{} {} mana", mana, impact.mana));
+    }
+    parts.join(", ")
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Difficulty {
     Normal,
     Hard,
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
-struct Game {
+/// A fight, with the spells from a [`SpellBook`]. Games are compared and hashed by their state
+/// alone, so only games with the same spell book should be compared
+#[derive(Debug, Clone, Copy)]
+pub struct Game<'a> {
+    spell_book: &'a SpellBook,
     player: Player,
     boss: Boss,
     effect_timers: EffectTimers,
     difficulty: Difficulty,
 }
 
-impl Game {
-    pub fn new(player: Player, boss: Boss, difficulty: Difficulty) -> Self {
+impl<'a> Game<'a> {
+    pub fn new(
+        spell_book: &'a SpellBook,
+        player: Player,
+        boss: Boss,
+        difficulty: Difficulty,
+    ) -> Self {
         Self {
+            spell_book,
             player,
             boss,
             effect_timers: EffectTimers::new(),
@@ -258,6 +648,10 @@ impl Game {
         }
     }
 
+    pub fn spell_book(&self) -> &'a SpellBook {
+        self.spell_book
+    }
+
     pub fn player(&self) -> Player {
         self.player
     }
@@ -266,8 +660,8 @@ impl Game {
         self.boss
     }
 
-    pub fn poison_timer(&self) -> u8 {
-        self.effect_timers.timer(Effect::Poison)
+    pub fn effect_timers(&self) -> EffectTimers {
+        self.effect_timers
     }
 
     pub fn winner(&self) -> Option<Winner> {
@@ -280,6 +674,35 @@ impl Game {
         }
     }
 
+    /// Replays `spells` from this state, one turn after the other in the style of the puzzle
+    /// description, with everything that happens in a turn in the order it happens. Fails if a
+    /// spell can't be cast, or the game ends before all were cast
+    pub fn battle_log(mut self, spells: &[Spell]) -> Result<String, GameError> {
+        let spell_book = self.spell_book;
+        let mut log = String::new();
+        for &spell in spells {
+            writeln!(log, "-- Player turn --\n{}", self).unwrap();
+            let winner = self.player_take_turn_observed(spell, &mut |event| {
+                writeln!(log, "{}", event.describe(spell_book)).unwrap()
+            })?;
+            log.push('\n');
+            if winner.is_some() {
+                continue;
+            }
+            writeln!(log, "-- Boss turn --\n{}", self).unwrap();
+            self.boss_take_turn_observed(&mut |event| {
+                writeln!(log, "{}", event.describe(spell_book)).unwrap()
+            })?;
+            log.push('\n');
+        }
+        log.push_str(match self.winner() {
+            Some(Winner::Player) => "The boss is dead, the player wins.",
+            Some(Winner::Boss) => "The player is dead, the boss wins.",
+            None => "The fight isn't over yet.",
+        });
+        Ok(log)
+    }
+
     pub fn play_round(&mut self, player_spell: Spell) -> Result<Option<Winner>, GameError> {
         if let Some(winner) = self.player_take_turn(player_spell)? {
             Ok(Some(winner))
@@ -289,21 +712,43 @@ impl Game {
     }
 
     pub fn player_take_turn(&mut self, spell: Spell) -> Result<Option<Winner>, GameError> {
+        self.player_take_turn_observed(spell, &mut |_| {})
+    }
+
+    pub fn boss_take_turn(&mut self) -> Result<Option<Winner>, GameError> {
+        self.boss_take_turn_observed(&mut |_| {})
+    }
+
+    /// Like [`Game::player_take_turn`], passing everything that happens to `observe`
+    pub fn player_take_turn_observed(
+        &mut self,
+        spell: Spell,
+        observe: &mut impl FnMut(TurnEvent),
+    ) -> Result<Option<Winner>, GameError> {
         self.assert_no_winner_yet()?;
         self.assert_player_can_cast(spell)?;
-        self.apply_player_difficulty_modifier()
-            .and_then(|()| self.apply_active_effects())
-            .and_then(|()| self.player_cast_spell(spell))
+        self.apply_player_difficulty_modifier(observe)
+            .and_then(|()| self.apply_active_effects(observe))
+            .and_then(|()| self.player_cast_spell(spell, observe))
             .map_or_else(|winner| Ok(Some(winner)), |()| Ok(None))
     }
 
-    pub fn boss_take_turn(&mut self) -> Result<Option<Winner>, GameError> {
+    /// Like [`Game::boss_take_turn`], passing everything that happens to `observe`
+    pub fn boss_take_turn_observed(
+        &mut self,
+        observe: &mut impl FnMut(TurnEvent),
+    ) -> Result<Option<Winner>, GameError> {
         self.assert_no_winner_yet()?;
-        self.apply_active_effects()
-            .and_then(|()| self.boss_attack())
+        self.apply_active_effects(observe)
+            .and_then(|()| self.boss_attack(observe))
             .map_or_else(|winner| Ok(Some(winner)), |()| Ok(None))
     }
 
+    /// Whether the player can cast `spell` on their next turn
+    pub fn can_cast(&self, spell: Spell) -> bool {
+        self.assert_player_can_cast(spell).is_ok()
+    }
+
     fn assert_no_winner_yet(&self) -> Result<(), GameError> {
         if self.winner().is_some() {
             Err(GameError::GameFinished)
@@ -312,15 +757,37 @@ impl Game {
         }
     }
 
+    /// The active effects with their remaining turns, e.g. `Shield (3 turns left)`
+    fn active_effects(&self) -> String {
+        let active: Vec<_> = self
+            .spell_book
+            .spells()
+            .filter(|&spell| self.effect_timers.is_active(spell))
+            .map(|spell| {
+                let timer = self.effect_timers.timer(spell);
+                format!(
+                    "{} ({} turn{} left)",
+                    self.spell_book.name(spell),
+                    timer,
+                    if timer > 1 { "s" } else { "" }
+                )
+            })
+            .collect();
+        if active.is_empty() {
+            "no active effects".to_owned()
+        } else {
+            active.join(", ")
+        }
+    }
+
     fn assert_player_can_cast(&self, spell: Spell) -> Result<(), GameError> {
-        if self.player.mana < spell.mana_cost() {
+        let definition = self.spell_book.get(spell).ok_or(GameError::UnknownSpell)?;
+        if self.player.mana < definition.mana_cost {
             return Err(GameError::NotEnoughMana);
         }
-        if let Some(effect) = spell.effect() {
-            if self.effect_timers.timer(effect) > 1 {
-                // timer of 1 will expire before player casts the spell again, so that's allowed
-                return Err(GameError::EffectAlreadyActive);
-            }
+        if self.effect_timers.timer(spell) > 1 {
+            // timer of 1 will expire before player casts the spell again, so that's allowed
+            return Err(GameError::EffectAlreadyActive);
         }
         Ok(())
     }
@@ -332,30 +799,55 @@ impl Game {
         }
     }
 
-    fn apply_player_difficulty_modifier(&mut self) -> Result<(), Winner> {
+    fn apply_player_difficulty_modifier(
+        &mut self,
+        observe: &mut impl FnMut(TurnEvent),
+    ) -> Result<(), Winner> {
         if self.difficulty == Difficulty::Hard {
             deal_damage(&mut self.player.hit_points, 1);
+            observe(TurnEvent::HardDifficulty);
         }
         self.winner_result()
     }
 
-    fn activate_effect(&mut self, effect: Effect, duration: u8) {
-        self.effect_timers.activate(effect, duration);
-        effect.activate(&mut self.player, &mut self.boss);
+    fn apply_impact(&mut self, impact: Impact) {
+        if impact.damage > 0 {
+            deal_damage(&mut self.boss.hit_points, impact.damage);
+        }
+        self.player.hit_points = self.player.hit_points.saturating_add(impact.heal);
+        self.player.mana = self.player.mana.saturating_add(impact.mana);
     }
 
-    fn apply_active_effects(&mut self) -> Result<(), Winner> {
-        self.apply_effect_if_active(Effect::Shield)?;
-        self.apply_effect_if_active(Effect::Poison)?;
-        self.apply_effect_if_active(Effect::Recharge)?;
+    fn apply_active_effects(&mut self, observe: &mut impl FnMut(TurnEvent)) -> Result<(), Winner> {
+        for spell in self.spell_book.spells() {
+            self.apply_effect_if_active(spell, observe)?;
+        }
         Ok(())
     }
 
-    fn apply_effect_if_active(&mut self, effect: Effect) -> Result<(), Winner> {
-        if let Ok(timer) = self.effect_timers.try_decrement(effect) {
-            effect.apply(&mut self.player, &mut self.boss);
+    fn apply_effect_if_active(
+        &mut self,
+        spell: Spell,
+        observe: &mut impl FnMut(TurnEvent),
+    ) -> Result<(), Winner> {
+        if let Ok(timer) = self.effect_timers.try_decrement(spell) {
+            let effect = self
+                .spell_book
+                .definition(spell)
+                .effect
+                .expect("only spells with an effect have a timer");
+            self.apply_impact(effect.each_turn);
+            observe(TurnEvent::EffectTicked {
+                spell,
+                impact: effect.each_turn,
+                timer,
+            });
             if timer == 0 {
-                effect.deactivate(&mut self.player, &mut self.boss);
+                self.player.armor = self.player.armor.saturating_sub(effect.armor);
+                observe(TurnEvent::EffectWoreOff {
+                    spell,
+                    armor: effect.armor,
+                });
             }
             self.winner_result()
         } else {
@@ -363,39 +855,73 @@ impl Game {
         }
     }
 
-    fn player_cast_spell(&mut self, spell: Spell) -> Result<(), Winner> {
-        spell.cast(self);
+    fn player_cast_spell(
+        &mut self,
+        spell: Spell,
+        observe: &mut impl FnMut(TurnEvent),
+    ) -> Result<(), Winner> {
+        let definition = self.spell_book.definition(spell);
+        self.player.mana -= definition.mana_cost;
+        self.apply_impact(definition.instant);
+        if let Some(effect) = definition.effect {
+            self.effect_timers.activate(spell, effect.turns);
+            self.player.armor = self.player.armor.saturating_add(effect.armor);
+        }
+        observe(TurnEvent::Cast(spell));
         self.winner_result()
     }
 
-    fn boss_attack(&mut self) -> Result<(), Winner> {
+    fn boss_attack(&mut self, observe: &mut impl FnMut(TurnEvent)) -> Result<(), Winner> {
         deal_damage(
             &mut self.player.hit_points,
             self.boss.damage.saturating_sub(self.player.armor),
         );
+        observe(TurnEvent::BossAttack {
+            damage: self.boss.damage,
+            armor: self.player.armor,
+        });
         self.winner_result()
     }
 }
 
-fn deal_damage(defender_hit_points: &mut u32, attacker_damage: u32) {
-    *defender_hit_points = defender_hit_points.saturating_sub(max(attacker_damage, 1));
+impl Game<'_> {
+    fn state(&self) -> (Player, Boss, EffectTimers, Difficulty) {
+        (self.player, self.boss, self.effect_timers, self.difficulty)
+    }
 }
 
-fn decrement_effect_counter(counter: &mut Option<u8>) -> Result<(), ()> {
-    match counter {
-        None => Err(()),
-        Some(ref mut count) => {
-            *count -= 1;
-            if *count == 0 {
-                *counter = None;
-            }
-            Ok(())
-        }
+impl PartialEq for Game<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.state() == other.state()
+    }
+}
+
+impl Eq for Game<'_> {}
+
+impl Hash for Game<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.state().hash(state)
     }
 }
 
+impl PartialOrd for Game<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Game<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.state().cmp(&other.state())
+    }
+}
+
+fn deal_damage(defender_hit_points: &mut u32, attacker_damage: u32) {
+    *defender_hit_points = defender_hit_points.saturating_sub(max(attacker_damage, 1));
+}
+
 #[derive(Debug, PartialEq, Eq)]
-enum Winner {
+pub enum Winner {
     Player,
     Boss,
 }
@@ -434,18 +960,18 @@ impl FromStr for Boss {
 }
 
 #[derive(PartialEq, Eq, Clone, Copy)]
-struct Node {
+struct Node<'a> {
     total_mana_cost: u32,
-    game_state: Game,
+    game_state: Game<'a>,
 }
 
-impl PartialOrd for Node {
+impl PartialOrd for Node<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl Ord for Node {
+impl Ord for Node<'_> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         // reverse ordering by mana cost to make BinaryHeap a min heap
         other
@@ -455,23 +981,62 @@ impl Ord for Node {
     }
 }
 
-struct DijkstraOptimizer {
-    node_distances: HashMap<Game, u32>,
-    unvisited: BinaryHeap<Node>,
+/// The cheapest wins found by [`DijkstraOptimizer::find_all_cheapest_wins`]
+#[derive(Debug, PartialEq, Eq)]
+pub struct CheapestWins {
+    pub mana_cost: u32,
+    /// How many spell sequences win for that cost, saturating at `u64::MAX`
+    pub count: u64,
+    /// Some of those sequences, sorted
+    pub sequences: Vec<Vec<Spell>>,
+}
+
+/// Why [`DijkstraOptimizer`] found no win
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchError {
+    /// Every sequence of spells ends with the player dead or out of mana
+    Unbeatable,
+    /// The search visited this many game states without finding a win. A spell book that heals
+    /// more than the boss hits and gives back more mana than it costs lets the player stall
+    /// forever, with no end to the states to search
+    TooManyStates(usize),
+}
+
+impl Display for SearchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unbeatable => write!(f, "the boss can't be beaten"),
+            Self::TooManyStates(max_states) => {
+                write!(f, "no win found within {} visited game states", max_states)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SearchError {}
+
+/// Finds the cheapest way (in mana) for the player to win a game
+pub struct DijkstraOptimizer<'a> {
+    max_states: usize,
+    num_visited: usize,
+    node_distances: HashMap<Game<'a>, u32>,
+    /// The states each game state was reached from most cheaply, and the spell cast to get there.
+    /// More than one if several paths tie
+    predecessors: HashMap<Game<'a>, Vec<(Game<'a>, Spell)>>,
+    unvisited: BinaryHeap<Node<'a>>,
 }
 
-impl DijkstraOptimizer {
-    const SPELLS: &[Spell] = &[
-        Spell::MagicMissile,
-        Spell::Drain,
-        Spell::Shield,
-        Spell::Poison,
-        Spell::Recharge,
-    ];
+impl<'a> DijkstraOptimizer<'a> {
+    /// How many game states a search visits by default before giving up
+    pub const DEFAULT_MAX_STATES: usize = 1_000_000;
 
-    fn new(initial_state: Game) -> Self {
+    pub fn new(initial_state: Game<'a>) -> Self {
         let mut self_ = Self {
-            node_distances: HashMap::new(),
+            max_states: Self::DEFAULT_MAX_STATES,
+            num_visited: 0,
+            // so that a round leading back to the start can't look like a way to get there
+            node_distances: HashMap::from([(initial_state, 0)]),
+            predecessors: HashMap::new(),
             unvisited: BinaryHeap::new(),
         };
         self_.register_neighbors(&Node {
@@ -481,51 +1046,192 @@ impl DijkstraOptimizer {
         self_
     }
 
-    fn find_lowest_mana_cost_to_win(mut self) -> Option<u32> {
-        while let Some(node) = self.unvisited.pop() {
+    /// Gives up with [`SearchError::TooManyStates`] once the search has visited `max_states` states
+    pub fn with_max_states(self, max_states: usize) -> Self {
+        Self { max_states, ..self }
+    }
+
+    pub fn find_lowest_mana_cost_to_win(self) -> Result<u32, SearchError> {
+        self.find_cheapest_win()
+            .map(|(total_mana_cost, _)| total_mana_cost)
+    }
+
+    /// Returns the lowest mana cost to win, along with the spells to cast in order
+    pub fn find_cheapest_win(mut self) -> Result<(u32, Vec<Spell>), SearchError> {
+        while let Some(node) = self.next_unvisited()? {
             if let Some(winner) = node.game_state.winner() {
                 match winner {
-                    Winner::Player => return Some(node.total_mana_cost), // reached goal!
+                    // reached goal!
+                    Winner::Player => {
+                        return Ok((node.total_mana_cost, self.spells_to(node.game_state)))
+                    }
                     Winner::Boss => unreachable!(), // filtered out before being pushed on heap
                 }
             }
 
+            self.register_neighbors(&node);
+        }
+        // No way to win
+        Err(SearchError::Unbeatable)
+    }
+
+    /// Returns the lowest mana cost to win, along with how many spell sequences win for that cost
+    /// and up to `max_sequences` of them. The number of sequences can grow exponentially with
+    /// their length, so they are counted without listing them all
+    pub fn find_all_cheapest_wins(
+        mut self,
+        max_sequences: usize,
+    ) -> Result<CheapestWins, SearchError> {
+        let mut lowest_mana_cost = None;
+        let mut wins = Vec::new();
+        while let Some(node) = self.next_unvisited()? {
+            if lowest_mana_cost.is_some_and(|cost| node.total_mana_cost > cost) {
+                break; // all remaining wins are more expensive
+            }
+            if let Some(winner) = node.game_state.winner() {
+                match winner {
+                    Winner::Player => {
+                        lowest_mana_cost = Some(node.total_mana_cost);
+                        wins.push(node.game_state);
+                        continue;
+                    }
+                    Winner::Boss => unreachable!(), // filtered out before being pushed on heap
+                }
+            }
+
+            self.register_neighbors(&node);
+        }
+        let mana_cost = lowest_mana_cost.ok_or(SearchError::Unbeatable)?;
+        let mut counts = HashMap::new();
+        let count = wins.iter().fold(0u64, |count, &game_state| {
+            count.saturating_add(self.count_spells_to(game_state, &mut counts))
+        });
+        let mut sequences = Vec::new();
+        for &game_state in &wins {
+            self.collect_spells_to(game_state, &mut Vec::new(), max_sequences, &mut sequences);
+        }
+        sequences.sort();
+        Ok(CheapestWins {
+            mana_cost,
+            count,
+            sequences,
+        })
+    }
+
+    /// The cheapest state left to visit, unless the search has visited too many already
+    fn next_unvisited(&mut self) -> Result<Option<Node<'a>>, SearchError> {
+        while let Some(node) = self.unvisited.pop() {
             if self.node_distances[&node.game_state] < node.total_mana_cost {
                 continue; // we already found a shorter path to this state
             }
+            if self.num_visited >= self.max_states {
+                return Err(SearchError::TooManyStates(self.max_states));
+            }
+            self.num_visited += 1;
+            return Ok(Some(node));
+        }
+        Ok(None)
+    }
 
-            self.register_neighbors(&node);
+    fn spells_to(&self, game_state: Game<'a>) -> Vec<Spell> {
+        let mut spells = Vec::new();
+        let mut current = game_state;
+        while let Some(&(predecessor, spell)) = self
+            .predecessors
+            .get(&current)
+            .and_then(|predecessors| predecessors.first())
+        {
+            spells.push(spell);
+            current = predecessor;
         }
-        // No way to win
-        None
+        spells.reverse();
+        spells
     }
 
-    fn register_neighbors(&mut self, current_node: &Node) {
-        for spell in Self::SPELLS {
-            self.register_neighbor(&current_node, *spell)
+    /// How many cheapest spell sequences lead to `game_state`, memoized in `counts`
+    fn count_spells_to(&self, game_state: Game<'a>, counts: &mut HashMap<Game<'a>, u64>) -> u64 {
+        if let Some(&count) = counts.get(&game_state) {
+            return count;
         }
+        let Some(predecessors) = self.predecessors.get(&game_state) else {
+            return 1;
+        };
+        // every spell costs mana, so predecessors are always cheaper and can't lead back here
+        let count = predecessors.iter().fold(0u64, |count, &(predecessor, _)| {
+            count.saturating_add(self.count_spells_to(predecessor, counts))
+        });
+        counts.insert(game_state, count);
+        count
     }
 
-    fn register_neighbor(&mut self, current_node: &Node, spell: Spell) {
+    /// Adds the cheapest spell sequences leading to `game_state` and ending in `suffix` (which is
+    /// reversed) to `sequences`, until there are `max_sequences`
+    fn collect_spells_to(
+        &self,
+        game_state: Game<'a>,
+        suffix: &mut Vec<Spell>,
+        max_sequences: usize,
+        sequences: &mut Vec<Vec<Spell>>,
+    ) {
+        if sequences.len() >= max_sequences {
+            return;
+        }
+        let Some(predecessors) = self.predecessors.get(&game_state) else {
+            sequences.push(suffix.iter().rev().copied().collect());
+            return;
+        };
+        for &(predecessor, spell) in predecessors {
+            suffix.push(spell);
+            self.collect_spells_to(predecessor, suffix, max_sequences, sequences);
+            suffix.pop();
+        }
+    }
+
+    fn register_neighbors(&mut self, current_node: &Node<'a>) {
+        for spell in current_node.game_state.spell_book().spells() {
+            self.register_neighbor(current_node, spell)
+        }
+    }
+
+    fn register_neighbor(&mut self, current_node: &Node<'a>, spell: Spell) {
         let mut neighbor_game_state = current_node.game_state;
         if let Ok(winner) = neighbor_game_state.play_round(spell) {
             if let Some(Winner::Boss) = winner {
                 return;
             }
 
-            let neighbor_cost = current_node.total_mana_cost + spell.mana_cost();
-            if neighbor_cost
-                < *self
-                    .node_distances
-                    .get(&neighbor_game_state)
-                    .unwrap_or(&u32::MAX)
-            {
+            if neighbor_game_state == current_node.game_state {
+                return; // the round changed nothing
+            }
+            let Some(neighbor_cost) = current_node
+                .total_mana_cost
+                .checked_add(neighbor_game_state.spell_book().definition(spell).mana_cost)
+            else {
+                return; // more mana than anyone could spend
+            };
+            let predecessor = (current_node.game_state, spell);
+            match neighbor_cost.cmp(
                 self.node_distances
-                    .insert(neighbor_game_state, neighbor_cost);
-                self.unvisited.push(Node {
-                    total_mana_cost: neighbor_cost,
-                    game_state: neighbor_game_state,
-                })
+                    .get(&neighbor_game_state)
+                    .unwrap_or(&u32::MAX),
+            ) {
+                Ordering::Less => {
+                    self.node_distances
+                        .insert(neighbor_game_state, neighbor_cost);
+                    self.predecessors
+                        .insert(neighbor_game_state, vec![predecessor]);
+                    self.unvisited.push(Node {
+                        total_mana_cost: neighbor_cost,
+                        game_state: neighbor_game_state,
+                    })
+                }
+                // another path just as cheap, remember it too
+                Ordering::Equal => self
+                    .predecessors
+                    .entry(neighbor_game_state)
+                    .or_default()
+                    .push(predecessor),
+                Ordering::Greater => {}
             }
         }
     }
@@ -548,22 +1254,119 @@ mod tests {
         assert_eq!(boss.damage, 9);
     }
 
+    #[test]
+    fn test_spell_book() {
+        let spell_book: SpellBook = r#"[
+            {"name": "Magic Missile", "cost": 53, "damage": 4},
+            {"name": "Drain", "cost": 73, "damage": 2, "heal": 2},
+            {"name": "Shield", "cost": 113, "effect": {"turns": 6, "armor": 7}},
+            {"name": "Poison", "cost": 173, "effect": {"turns": 6, "damage": 3}},
+            {"name": "Recharge", "cost": 229, "effect": {"turns": 5, "mana": 101}}
+        ]"#
+        .parse()
+        .unwrap();
+        assert_eq!(spell_book, SpellBook::default());
+        assert_eq!(spell_book.find("recharge"), Some(Spell::RECHARGE));
+        assert_eq!(
+            spell_book.names(&[Spell::POISON, Spell::MAGIC_MISSILE]),
+            "Poison, Magic Missile"
+        );
+
+        for (json, error) in [
+            ("{}", "expected an array of spells"),
+            ("[]", "expected 1 to 16 spells, got 0"),
+            (r#"[{"cost": 1}]"#, "expected a name for every spell"),
+            (r#"[{"name": "Nap"}]"#, "Nap: expected a cost"),
+            (
+                r#"[{"name": "Nap", "cost": -1}]"#,
+                "Nap: cost: expected a non-negative number in range",
+            ),
+            (
+                r#"[{"name": "Nap", "cost": 1, "sleep": 8}]"#,
+                "Nap: unknown field \"sleep\"",
+            ),
+            (
+                r#"[{"name": "Nap", "cost": 1, "effect": {"heal": 1}}]"#,
+                "Nap: expected the number of turns the effect lasts",
+            ),
+            (
+                r#"[{"name": "Nap", "cost": 1, "effect": {"turns": 0}}]"#,
+                "Nap: the effect must last at least 1 turn",
+            ),
+            (
+                r#"[{"name": "Nap", "cost": 1}, {"name": "NAP", "cost": 2}]"#,
+                "NAP: defined twice",
+            ),
+            (
+                r#"[{"name": "Nap", "cost": 0, "heal": 5}, {"name": "Zap", "cost": 10, "damage": 20}]"#,
+                "Nap: must cost at least 1 mana",
+            ),
+        ] {
+            assert_eq!(json.parse::<SpellBook>(), Err(error.to_owned()), "{}", json);
+        }
+    }
+
+    #[test]
+    fn test_custom_spell_book() {
+        let spell_book: SpellBook = r#"[
+            {"name": "Fireball", "cost": 10, "damage": 5},
+            {"name": "Burn", "cost": 12, "effect": {"turns": 4, "damage": 2}}
+        ]"#
+        .parse()
+        .unwrap();
+        let game = Game::new(
+            &spell_book,
+            Player::new(10, 250),
+            Boss::new(13, 4),
+            Difficulty::Normal,
+        );
+        let fireball = spell_book.find("Fireball").unwrap();
+        assert_eq!(
+            DijkstraOptimizer::new(game).find_all_cheapest_wins(10),
+            Ok(CheapestWins {
+                mana_cost: 30,
+                count: 1,
+                sequences: vec![vec![fireball; 3]]
+            })
+        );
+
+        let mut burning = game;
+        let burn = spell_book.find("Burn").unwrap();
+        assert_eq!(burning.play_round(burn), Ok(None));
+        assert_eq!(burning.active_effects(), "Burn (3 turns left)");
+        assert_eq!(
+            burning.play_round(burn),
+            Err(GameError::EffectAlreadyActive)
+        );
+        assert_eq!(
+            burning.play_round(Spell::RECHARGE),
+            Err(GameError::UnknownSpell)
+        );
+    }
+
     #[test]
     fn test_game_scenario_1() {
-        let mut game = Game::new(Player::new(10, 250), Boss::new(13, 8), Difficulty::Normal);
+        let spell_book = SpellBook::default();
+        let mut game = Game::new(
+            &spell_book,
+            Player::new(10, 250),
+            Boss::new(13, 8),
+            Difficulty::Normal,
+        );
         assert_eq!(
             game.player().to_string(),
             "Player has 10 hit points, 0 armor, 250 mana"
         );
         assert_eq!(game.boss().to_string(), "Boss has 13 hit points");
 
-        assert_eq!(game.player_take_turn(Spell::Poison), Ok(None));
+        assert_eq!(game.player_take_turn(Spell::POISON), Ok(None));
         assert_eq!(
             game.player().to_string(),
             "Player has 10 hit points, 0 armor, 77 mana"
         );
         assert_eq!(game.boss().to_string(), "Boss has 13 hit points");
-        assert_eq!(game.poison_timer(), 6);
+        assert_eq!(game.effect_timers().timer(Spell::POISON), 6);
+        assert_eq!(game.active_effects(), "Poison (6 turns left)");
 
         assert_eq!(game.boss_take_turn(), Ok(None));
         assert_eq!(
@@ -571,9 +1374,9 @@ mod tests {
             "Player has 2 hit points, 0 armor, 77 mana"
         );
         assert_eq!(game.boss().to_string(), "Boss has 10 hit points");
-        assert_eq!(game.poison_timer(), 5);
+        assert_eq!(game.effect_timers().timer(Spell::POISON), 5);
 
-        assert_eq!(game.player_take_turn(Spell::MagicMissile), Ok(None));
+        assert_eq!(game.player_take_turn(Spell::MAGIC_MISSILE), Ok(None));
         assert_eq!(
             game.player().to_string(),
             "Player has 2 hit points, 0 armor, 24 mana"
@@ -585,13 +1388,19 @@ mod tests {
 
     #[test]
     fn test_game_scenario_2() {
-        let mut game = Game::new(Player::new(10, 250), Boss::new(14, 8), Difficulty::Normal);
+        let spell_book = SpellBook::default();
+        let mut game = Game::new(
+            &spell_book,
+            Player::new(10, 250),
+            Boss::new(14, 8),
+            Difficulty::Normal,
+        );
         assert_eq!(
             game.player().to_string(),
             "Player has 10 hit points, 0 armor, 250 mana"
         );
         assert_eq!(game.boss().to_string(), "Boss has 14 hit points");
-        assert_eq!(game.player_take_turn(Spell::Recharge), Ok(None));
+        assert_eq!(game.player_take_turn(Spell::RECHARGE), Ok(None));
 
         assert_eq!(
             game.player().to_string(),
@@ -599,15 +1408,15 @@ mod tests {
         );
         assert_eq!(game.boss().to_string(), "Boss has 14 hit points");
         assert_eq!(game.boss_take_turn(), Ok(None));
-        assert_eq!(game.effect_timers.recharge_timer, 4);
+        assert_eq!(game.effect_timers.timer(Spell::RECHARGE), 4);
 
         assert_eq!(
             game.player().to_string(),
             "Player has 2 hit points, 0 armor, 122 mana"
         );
         assert_eq!(game.boss().to_string(), "Boss has 14 hit points");
-        assert_eq!(game.player_take_turn(Spell::Shield), Ok(None));
-        assert_eq!(game.effect_timers.recharge_timer, 3);
+        assert_eq!(game.player_take_turn(Spell::SHIELD), Ok(None));
+        assert_eq!(game.effect_timers.timer(Spell::RECHARGE), 3);
 
         assert_eq!(
             game.player().to_string(),
@@ -615,17 +1424,21 @@ mod tests {
         );
         assert_eq!(game.boss().to_string(), "Boss has 14 hit points");
         assert_eq!(game.boss_take_turn(), Ok(None));
-        assert_eq!(game.effect_timers.shield_timer, 5);
-        assert_eq!(game.effect_timers.recharge_timer, 2);
+        assert_eq!(game.effect_timers.timer(Spell::SHIELD), 5);
+        assert_eq!(game.effect_timers.timer(Spell::RECHARGE), 2);
 
         assert_eq!(
             game.player().to_string(),
             "Player has 1 hit point, 7 armor, 211 mana"
         );
         assert_eq!(game.boss().to_string(), "Boss has 14 hit points");
-        assert_eq!(game.player_take_turn(Spell::Drain), Ok(None));
-        assert_eq!(game.effect_timers.shield_timer, 4);
-        assert_eq!(game.effect_timers.recharge_timer, 1);
+        assert_eq!(game.player_take_turn(Spell::DRAIN), Ok(None));
+        assert_eq!(game.effect_timers.timer(Spell::SHIELD), 4);
+        assert_eq!(game.effect_timers.timer(Spell::RECHARGE), 1);
+        assert_eq!(
+            game.active_effects(),
+            "Shield (4 turns left), Recharge (1 turn left)"
+        );
 
         assert_eq!(
             game.player().to_string(),
@@ -633,16 +1446,16 @@ mod tests {
         );
         assert_eq!(game.boss().to_string(), "Boss has 12 hit points");
         assert_eq!(game.boss_take_turn(), Ok(None));
-        assert_eq!(game.effect_timers.shield_timer, 3);
-        assert_eq!(game.effect_timers.recharge_timer, 0);
+        assert_eq!(game.effect_timers.timer(Spell::SHIELD), 3);
+        assert_eq!(game.effect_timers.timer(Spell::RECHARGE), 0);
 
         assert_eq!(
             game.player().to_string(),
             "Player has 2 hit points, 7 armor, 340 mana"
         );
         assert_eq!(game.boss().to_string(), "Boss has 12 hit points");
-        assert_eq!(game.player_take_turn(Spell::Poison), Ok(None));
-        assert_eq!(game.effect_timers.shield_timer, 2);
+        assert_eq!(game.player_take_turn(Spell::POISON), Ok(None));
+        assert_eq!(game.effect_timers.timer(Spell::SHIELD), 2);
 
         assert_eq!(
             game.player().to_string(),
@@ -650,17 +1463,17 @@ mod tests {
         );
         assert_eq!(game.boss().to_string(), "Boss has 12 hit points");
         assert_eq!(game.boss_take_turn(), Ok(None));
-        assert_eq!(game.effect_timers.poison_timer, 5);
-        assert_eq!(game.effect_timers.shield_timer, 1);
+        assert_eq!(game.effect_timers.timer(Spell::POISON), 5);
+        assert_eq!(game.effect_timers.timer(Spell::SHIELD), 1);
 
         assert_eq!(
             game.player().to_string(),
             "Player has 1 hit point, 7 armor, 167 mana"
         );
         assert_eq!(game.boss().to_string(), "Boss has 9 hit points");
-        assert_eq!(game.player_take_turn(Spell::MagicMissile), Ok(None));
-        assert_eq!(game.effect_timers.poison_timer, 4);
-        assert_eq!(game.effect_timers.shield_timer, 0);
+        assert_eq!(game.player_take_turn(Spell::MAGIC_MISSILE), Ok(None));
+        assert_eq!(game.effect_timers.timer(Spell::POISON), 4);
+        assert_eq!(game.effect_timers.timer(Spell::SHIELD), 0);
 
         assert_eq!(
             game.player().to_string(),
@@ -669,4 +1482,242 @@ mod tests {
         assert_eq!(game.boss().to_string(), "Boss has 2 hit points");
         assert_eq!(game.boss_take_turn(), Ok(Some(Winner::Player)));
     }
+
+    #[test]
+    fn test_find_cheapest_win() {
+        let spell_book = SpellBook::default();
+        let initial_state = Game::new(
+            &spell_book,
+            Player::new(10, 250),
+            Boss::new(13, 8),
+            Difficulty::Normal,
+        );
+        let (total_mana_cost, spells) = DijkstraOptimizer::new(initial_state)
+            .find_cheapest_win()
+            .unwrap();
+        assert_eq!(spells, vec![Spell::POISON, Spell::MAGIC_MISSILE]);
+        assert_eq!(total_mana_cost, 226);
+
+        let mut game = initial_state;
+        let mut winner = None;
+        for spell in &spells {
+            winner = game.play_round(*spell).unwrap();
+        }
+        assert_eq!(winner, Some(Winner::Player));
+        assert_eq!(
+            spells
+                .iter()
+                .map(|&spell| spell_book.definition(spell).mana_cost)
+                .sum::<u32>(),
+            total_mana_cost
+        );
+    }
+
+    #[test]
+    fn test_find_all_cheapest_wins() {
+        let spell_book = SpellBook::default();
+        let game = Game::new(
+            &spell_book,
+            Player::new(10, 250),
+            Boss::new(10, 5),
+            Difficulty::Normal,
+        );
+        assert_eq!(
+            DijkstraOptimizer::new(game).find_all_cheapest_wins(10),
+            Ok(CheapestWins {
+                mana_cost: 179,
+                count: 2,
+                sequences: vec![
+                    vec![Spell::MAGIC_MISSILE, Spell::DRAIN, Spell::MAGIC_MISSILE],
+                    vec![Spell::DRAIN, Spell::MAGIC_MISSILE, Spell::MAGIC_MISSILE],
+                ]
+            })
+        );
+
+        // two interchangeable spells for each of the 40 turns
+        let spell_book: SpellBook = r#"[
+            {"name": "Left", "cost": 10, "damage": 1},
+            {"name": "Right", "cost": 10, "damage": 1}
+        ]"#
+        .parse()
+        .unwrap();
+        let game = Game::new(
+            &spell_book,
+            Player::new(100, 1000),
+            Boss::new(40, 1),
+            Difficulty::Normal,
+        );
+        let wins = DijkstraOptimizer::new(game)
+            .find_all_cheapest_wins(3)
+            .unwrap();
+        assert_eq!(wins.mana_cost, 400);
+        assert_eq!(wins.count, 1 << 40);
+        assert_eq!(wins.sequences.len(), 3);
+        assert!(wins.sequences.iter().all(|spells| spells.len() == 40));
+    }
+
+    #[test]
+    fn test_search_ends() {
+        // healing as much as the boss hits keeps the player where they were
+        let spell_book: SpellBook = r#"[
+            {"name": "Nap", "cost": 1, "heal": 5},
+            {"name": "Zap", "cost": 10, "damage": 20}
+        ]"#
+        .parse()
+        .unwrap();
+        let zap = spell_book.find("Zap").unwrap();
+        let game = Game::new(
+            &spell_book,
+            Player::new(10, 100),
+            Boss::new(40, 5),
+            Difficulty::Normal,
+        );
+        assert_eq!(
+            DijkstraOptimizer::new(game).find_all_cheapest_wins(10),
+            Ok(CheapestWins {
+                mana_cost: 20,
+                count: 1,
+                sequences: vec![vec![zap, zap]],
+            })
+        );
+
+        // every nap gives a hit point and pays for itself, but zapping is out of reach
+        let spell_book: SpellBook = r#"[
+            {"name": "Nap", "cost": 2, "heal": 6, "effect": {"turns": 2, "mana": 1}},
+            {"name": "Zap", "cost": 1000, "damage": 100}
+        ]"#
+        .parse()
+        .unwrap();
+        let game = Game::new(
+            &spell_book,
+            Player::new(10, 10),
+            Boss::new(100, 5),
+            Difficulty::Normal,
+        );
+        assert_eq!(
+            DijkstraOptimizer::new(game)
+                .with_max_states(10_000)
+                .find_lowest_mana_cost_to_win(),
+            Err(SearchError::TooManyStates(10_000))
+        );
+
+        // too weak to survive a single turn
+        let game = Game::new(
+            &spell_book,
+            Player::new(1, 10),
+            Boss::new(100, 5),
+            Difficulty::Hard,
+        );
+        assert_eq!(
+            DijkstraOptimizer::new(game).find_lowest_mana_cost_to_win(),
+            Err(SearchError::Unbeatable)
+        );
+
+        // no overflow when healing or recharging at the limits
+        let spell_book = SpellBook::default();
+        let game = Game::new(
+            &spell_book,
+            Player::new(u32::MAX, u32::MAX),
+            Boss::new(13, 8),
+            Difficulty::Normal,
+        );
+        assert_eq!(
+            DijkstraOptimizer::new(game).find_lowest_mana_cost_to_win(),
+            Ok(4 * 53)
+        );
+        let mut game = game;
+        game.play_round(Spell::RECHARGE).unwrap();
+        game.play_round(Spell::DRAIN).unwrap();
+        assert_eq!(game.player().hit_points(), u32::MAX - 2 * 8 + 2);
+        // the third tick of recharge would go past the maximum
+        assert_eq!(game.player().mana(), u32::MAX);
+    }
+
+    #[test]
+    fn test_battle_log() {
+        let spell_book = SpellBook::default();
+        let game = Game::new(
+            &spell_book,
+            Player::new(10, 250),
+            Boss::new(13, 8),
+            Difficulty::Normal,
+        );
+        assert_eq!(
+            game.battle_log(&[Spell::POISON, Spell::MAGIC_MISSILE])
+                .unwrap(),
+            "\
+-- Player turn --
+- Player has 10 hit points, 0 armor, 250 mana
+- Boss has 13 hit points
+- no active effects
+Player casts Poison.
+
+-- Boss turn --
+- Player has 10 hit points, 0 armor, 77 mana
+- Boss has 13 hit points
+- Poison (6 turns left)
+Poison deals 3 damage; its timer is now 5.
+Boss attacks for 8 damage.
+
+-- Player turn --
+- Player has 2 hit points, 0 armor, 77 mana
+- Boss has 10 hit points
+- Poison (5 turns left)
+Poison deals 3 damage; its timer is now 4.
+Player casts Magic Missile, dealing 4 damage.
+
+-- Boss turn --
+- Player has 2 hit points, 0 armor, 24 mana
+- Boss has 3 hit points
+- Poison (4 turns left)
+Poison deals 3 damage; its timer is now 3.
+
+The boss is dead, the player wins."
+        );
+        assert_eq!(
+            game.battle_log(&[Spell::POISON, Spell::MAGIC_MISSILE, Spell::MAGIC_MISSILE]),
+            Err(GameError::GameFinished)
+        );
+
+        // the second example of the puzzle description
+        let game = Game::new(
+            &spell_book,
+            Player::new(10, 250),
+            Boss::new(14, 8),
+            Difficulty::Normal,
+        );
+        let log = game
+            .battle_log(&[
+                Spell::RECHARGE,
+                Spell::SHIELD,
+                Spell::DRAIN,
+                Spell::POISON,
+                Spell::MAGIC_MISSILE,
+            ])
+            .unwrap();
+        for line in [
+            "Recharge provides 101 mana; its timer is now 4.",
+            "Recharge wears off.",
+            "Player casts Shield, increasing armor by 7.",
+            "Shield's timer is now 5.",
+            "Boss attacks for 8 - 7 = 1 damage.",
+            "Player casts Drain, dealing 2 damage, healing 2 hit points.",
+            "Shield wears off, decreasing armor by 7.",
+        ] {
+            assert!(log.lines().any(|logged| logged == line), "{}", line);
+        }
+        assert!(log.ends_with("The boss is dead, the player wins."));
+
+        let game = Game::new(
+            &spell_book,
+            Player::new(10, 250),
+            Boss::new(13, 8),
+            Difficulty::Hard,
+        );
+        let log = game
+            .battle_log(&[Spell::POISON, Spell::MAGIC_MISSILE])
+            .unwrap();
+        assert_eq!(log.matches("Player loses 1 hit point.").count(), 2);
+        assert!(log.ends_with("Player loses 1 hit point.\n\nThe player is dead, the boss wins."));
+    }
 }
@@ -1,148 +1,1022 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Display};
+use std::hash::Hash;
 use std::str::FromStr;
 
-use super::Challenge;
+use num_bigint::BigUint;
+
+use super::parsing::{self, Parser};
+use super::{parse_lines, Challenge, ChallengeError, ChallengeResult, LineError};
 
 pub struct Day23 {
     instructions: Vec<Instruction>,
 }
 
+impl Day23 {
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+}
+
 impl Challenge for Day23 {
     const DAY: u8 = 23;
 
     type Part1Solution = u32;
     type Part2Solution = u32;
 
-    fn new(input: &str) -> Self {
-        Self {
-            instructions: input
-                .lines()
-                .map(|line| line.parse::<Instruction>().unwrap())
-                .collect(),
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Ok(Self {
+            instructions: parse_lines(input)?,
+        })
+    }
+
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        self.run_with_a(0)
+    }
+
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        self.run_with_a(1)
+    }
+}
+
+impl Day23 {
+    /// The value of b after running the program with `a` as the initial value of a
+    fn run_with_a(&self, a: u32) -> ChallengeResult<u32> {
+        let mut computer: Computer = Computer::with_instructions(self.instructions.clone());
+        let result = computer
+            .registers_mut()
+            .set(Register::A, a)
+            .and_then(|()| computer.run())
+            .and_then(|()| computer.registers().get(Register::B).copied());
+        result.map_err(|error| ChallengeError::new(format!("{} at pc {}", error, computer.pc())))
+    }
+}
+
+/// A value a [`Computer`] register can hold. Arithmetic is checked, so a program that overflows
+/// fails instead of computing garbage; [`BigUint`] registers only fail when going below zero, and
+/// only `i64` registers hold negative values
+pub trait Word: Clone + fmt::Debug + Display + Eq + Ord + Hash {
+    fn from_u32(value: u32) -> Self;
+    /// The value of a constant operand, None if the word can't hold it
+    fn from_i64(value: i64) -> Option<Self>;
+    /// The value as an offset for jumps, None if it's too large
+    fn to_i64(&self) -> Option<i64>;
+    fn checked_add(&self, rhs: &Self) -> Option<Self>;
+    fn checked_sub(&self, rhs: &Self) -> Option<Self>;
+    fn checked_mul(&self, rhs: &Self) -> Option<Self>;
+    fn half(&self) -> Self;
+    fn is_even(&self) -> bool;
+}
+
+macro_rules! impl_word_for_primitive {
+    ($($t:ty),*) => {$(
+        impl Word for $t {
+            fn from_u32(value: u32) -> Self {
+                value.into()
+            }
+
+            fn from_i64(value: i64) -> Option<Self> {
+                <$t>::try_from(value).ok()
+            }
+
+            fn to_i64(&self) -> Option<i64> {
+                i64::try_from(*self).ok()
+            }
+
+            fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                <$t>::checked_add(*self, *rhs)
+            }
+
+            fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+                <$t>::checked_sub(*self, *rhs)
+            }
+
+            fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                <$t>::checked_mul(*self, *rhs)
+            }
+
+            fn half(&self) -> Self {
+                self / 2
+            }
+
+            fn is_even(&self) -> bool {
+                self % 2 == 0
+            }
+        }
+    )*};
+}
+
+impl_word_for_primitive!(u32, u64, i64);
+
+impl Word for BigUint {
+    fn from_u32(value: u32) -> Self {
+        value.into()
+    }
+
+    fn from_i64(value: i64) -> Option<Self> {
+        BigUint::try_from(value).ok()
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        i64::try_from(self).ok()
+    }
+
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(self + rhs)
+    }
+
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        (self >= rhs).then(|| self - rhs)
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        Some(self * rhs)
+    }
+
+    fn half(&self) -> Self {
+        self >> 1
+    }
+
+    fn is_even(&self) -> bool {
+        !self.bit(0)
+    }
+}
+
+/// Why a [`Computer`] couldn't execute an instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionError {
+    /// The result doesn't fit into a register
+    Overflow,
+    /// The instruction refers to a register beyond the configured number of registers
+    UnknownRegister(Register),
+    /// The program would run forever
+    InfiniteLoop,
+}
+
+impl Display for ExecutionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExecutionError::Overflow => write!(f, "register overflow"),
+            ExecutionError::UnknownRegister(register) => write!(f, "no register {}", register),
+            ExecutionError::InfiniteLoop => write!(f, "infinite loop"),
+        }
+    }
+}
+
+/// More registers were requested than there are letters to name them by, see
+/// [`Register::MAX_COUNT`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooManyRegisters(pub usize);
+
+impl Display for TooManyRegisters {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} registers, but there are at most {}",
+            self.0,
+            Register::MAX_COUNT
+        )
+    }
+}
+
+/// The registers of a [`Computer`], all starting out as zero
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Registers<W> {
+    values: Vec<W>,
+}
+
+impl<W: Word> Registers<W> {
+    pub fn new(count: usize) -> Result<Self, TooManyRegisters> {
+        if count > Register::MAX_COUNT {
+            return Err(TooManyRegisters(count));
         }
+        Ok(Self {
+            values: vec![W::from_u32(0); count],
+        })
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
-        let mut computer = Computer::with_instructions(self.instructions.clone());
-        computer.run();
-        computer.b
+    pub fn get(&self, register: Register) -> Result<&W, ExecutionError> {
+        self.values
+            .get(register.index())
+            .ok_or(ExecutionError::UnknownRegister(register))
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
-        let mut computer = Computer::with_instructions(self.instructions.clone());
-        computer.a = 1;
-        computer.run();
-        computer.b
+    pub fn get_mut(&mut self, register: Register) -> Result<&mut W, ExecutionError> {
+        self.values
+            .get_mut(register.index())
+            .ok_or(ExecutionError::UnknownRegister(register))
+    }
+
+    pub fn set(&mut self, register: Register, value: impl Into<W>) -> Result<(), ExecutionError> {
+        *self.get_mut(register)? = value.into();
+        Ok(())
+    }
+
+    /// Replaces the value of `register` by `operation` applied to it, failing if that overflows
+    pub fn update(
+        &mut self,
+        register: Register,
+        operation: impl FnOnce(&W) -> Option<W>,
+    ) -> Result<(), ExecutionError> {
+        let value = self.get_mut(register)?;
+        *value = operation(value).ok_or(ExecutionError::Overflow)?;
+        Ok(())
     }
 }
 
-struct Computer {
-    a: u32,
-    b: u32,
+/// Lists the registers by name, e.g. `a=1 b=0`
+impl<W: Word> Display for Registers<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, value) in self.values.iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}={}", Register(index as u8), value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Where to continue after executing an instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Flow<W> {
+    /// With the next instruction
+    Next,
+    /// With the instruction this far from the current one
+    Jump(i64),
+    /// With the next instruction, after emitting a value
+    Output(W),
+    /// With the next instruction, after toggling the instruction this far from the current one
+    Toggle(i64),
+}
+
+/// An instruction set a [`Computer`] with `W` registers can run. New opcodes are added by
+/// implementing it for another instruction type, like [`Assembunny`]
+pub trait Operation<W: Word>: Clone + fmt::Debug + Display {
+    fn execute(&self, registers: &mut Registers<W>) -> Result<Flow<W>, ExecutionError>;
+
+    /// What a `tgl` turns this instruction into, for instruction sets that have one
+    fn toggled(&self) -> Self {
+        self.clone()
+    }
+
+    /// Recognizes a loop starting at `head` whose effect on the registers can be computed without
+    /// stepping through it, e.g. one that adds a register to another. Applies the effect and
+    /// returns the offset from `head` to continue at, or None to step through the loop as usual
+    fn fast_forward(
+        _program: &[Self],
+        _head: usize,
+        _registers: &mut Registers<W>,
+    ) -> Result<Option<i64>, ExecutionError> {
+        Ok(None)
+    }
+}
+
+/// How often [`Computer::run`] has to jump back to the same instruction before the loop starting
+/// there counts as hot and gets a chance to be fast-forwarded
+const HOT_LOOP_ARRIVALS: u32 = 3;
+
+/// The computer from the puzzle, but with any number of registers of width `W` and any instruction
+/// set. Runs until the program counter leaves the program
+#[derive(Debug, Clone)]
+pub struct Computer<W = u32, I = Instruction> {
+    registers: Registers<W>,
     pc: u32,
-    instructions: Vec<Instruction>,
+    instructions: Vec<I>,
+    output: Vec<W>,
+    fast_forward: bool,
 }
 
-impl Computer {
-    fn new() -> Self {
-        Computer {
-            a: 0,
-            b: 0,
+impl<W: Word, I: Operation<W>> Computer<W, I> {
+    /// A computer with the two registers of the puzzle
+    pub fn new() -> Self {
+        Self::with_register_count(2).expect("there are letters for two registers")
+    }
+
+    pub fn with_register_count(count: usize) -> Result<Self, TooManyRegisters> {
+        Ok(Computer {
+            registers: Registers::new(count)?,
             pc: 0,
             instructions: Vec::new(),
+            output: Vec::new(),
+            fast_forward: true,
+        })
+    }
+
+    pub fn with_instructions(instructions: impl IntoIterator<Item = I>) -> Self {
+        Self::new().with_program(instructions)
+    }
+
+    pub fn with_program(mut self, instructions: impl IntoIterator<Item = I>) -> Self {
+        self.load(instructions);
+        self
+    }
+
+    /// Replaces the program, keeping the registers
+    pub fn load(&mut self, instructions: impl IntoIterator<Item = I>) {
+        self.instructions = instructions.into_iter().collect();
+    }
+
+    /// Whether [`Computer::run`] skips the loops the instruction set recognizes, see
+    /// [`Operation::fast_forward`]. On by default
+    pub fn set_fast_forward(&mut self, fast_forward: bool) {
+        self.fast_forward = fast_forward;
+    }
+
+    /// Runs until the program counter leaves the program. Every jump back to an earlier instruction
+    /// closes a loop: once a loop is hot, the instruction set gets to fast-forward through it, and
+    /// coming back to a loop with the same registers as before means the program never halts,
+    /// which fails with [`ExecutionError::InfiniteLoop`] instead of hanging. The repetition is
+    /// found with [`CycleDetector`], so however long the program runs, only one earlier state is
+    /// kept around
+    pub fn run(&mut self) -> Result<(), ExecutionError> {
+        let mut arrivals: HashMap<u32, u32> = HashMap::new();
+        let mut cycle_detector = CycleDetector::default();
+        while let Some(instruction) = self.current_instruction() {
+            let pc = self.pc;
+            if self.execute(&instruction)? {
+                // the state kept so far was for a different program
+                cycle_detector = CycleDetector::default();
+            }
+            if self.pc > pc {
+                continue;
+            }
+            if cycle_detector.repeats(self.pc, &self.registers) {
+                return Err(ExecutionError::InfiniteLoop);
+            }
+            let arrivals = arrivals.entry(self.pc).or_default();
+            // only needs to tell whether a loop is hot, so it can't overflow however long this runs
+            *arrivals = arrivals.saturating_add(1);
+            if self.fast_forward && *arrivals >= HOT_LOOP_ARRIVALS {
+                if let Some(offset) =
+                    I::fast_forward(&self.instructions, self.pc as usize, &mut self.registers)?
+                {
+                    self.jump(offset);
+                }
+            }
         }
+        Ok(())
     }
 
-    fn with_instructions(instructions: impl IntoIterator<Item = Instruction>) -> Self {
-        let mut self_ = Self::new();
-        self_.instructions = instructions.into_iter().collect();
-        self_
+    /// Executes the current instruction and returns it, or None if the program has ended. The
+    /// program counter stays put if the instruction fails
+    pub fn step(&mut self) -> Result<Option<I>, ExecutionError> {
+        let Some(instruction) = self.current_instruction() else {
+            return Ok(None);
+        };
+        self.execute(&instruction)?;
+        Ok(Some(instruction))
     }
 
-    fn run(&mut self) {
+    /// Executes `instruction` as the one at the program counter, returns whether it changed the
+    /// program
+    fn execute(&mut self, instruction: &I) -> Result<bool, ExecutionError> {
+        match instruction.execute(&mut self.registers)? {
+            Flow::Next => self.jump(1),
+            Flow::Jump(offset) => self.jump(offset),
+            Flow::Output(value) => {
+                self.output.push(value);
+                self.jump(1);
+            }
+            Flow::Toggle(offset) => {
+                let target = i64::from(self.pc) + offset;
+                if let Some(target) = usize::try_from(target)
+                    .ok()
+                    .and_then(|target| self.instructions.get_mut(target))
+                {
+                    *target = target.toggled();
+                    self.jump(1);
+                    return Ok(true);
+                }
+                self.jump(1);
+            }
+        }
+        Ok(false)
+    }
+
+    pub fn pc(&self) -> u32 {
+        self.pc
+    }
+
+    pub fn instructions(&self) -> &[I] {
+        &self.instructions
+    }
+
+    pub fn current_instruction(&self) -> Option<I> {
+        self.instructions.get(self.pc as usize).cloned()
+    }
+
+    pub fn registers(&self) -> &Registers<W> {
+        &self.registers
+    }
+
+    pub fn registers_mut(&mut self) -> &mut Registers<W> {
+        &mut self.registers
+    }
+
+    /// Everything emitted by `out` instructions so far
+    pub fn output(&self) -> &[W] {
+        &self.output
+    }
+
+    fn jump(&mut self, offset: i64) {
+        self.pc = u32::try_from(i64::from(self.pc) + offset).unwrap_or(u32::MAX);
+    }
+}
+
+impl<W: Word, I: Operation<W>> Default for Computer<W, I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Brent's cycle detection over the states of a [`Computer`] at its loop heads, each of which
+/// determines the next: keeps a single earlier state and replaces it whenever the number of states
+/// since it was kept reaches the next power of two. Once the program cycles, the kept state comes
+/// around again within twice the length of the lead-in and the cycle
+#[derive(Debug)]
+struct CycleDetector<W> {
+    kept: Option<(u32, Registers<W>)>,
+    since_kept: u64,
+    power: u64,
+}
+
+impl<W> Default for CycleDetector<W> {
+    fn default() -> Self {
+        Self {
+            kept: None,
+            since_kept: 0,
+            power: 1,
+        }
+    }
+}
+
+impl<W: Word> CycleDetector<W> {
+    /// Whether the computer is back in the kept state, which means it never halts
+    fn repeats(&mut self, pc: u32, registers: &Registers<W>) -> bool {
+        if let Some((kept_pc, kept_registers)) = &self.kept {
+            if *kept_pc == pc && kept_registers == registers {
+                return true;
+            }
+        }
+        self.since_kept += 1;
+        if self.since_kept == self.power {
+            self.kept = Some((pc, registers.clone()));
+            self.since_kept = 0;
+            self.power *= 2;
+        }
+        false
+    }
+}
+
+/// Runs a [`Computer`] under control: single steps or until a breakpoint is hit, counting how
+/// often each instruction is executed and optionally recording a trace. Gives up after
+/// `max_cycles` executed instructions, so programs that never halt (e.g. `jmp +0`) can be debugged
+#[derive(Debug)]
+pub struct Debugger<W = u32, I = Instruction> {
+    computer: Computer<W, I>,
+    breakpoints: Vec<Breakpoint>,
+    max_cycles: u64,
+    cycles: u64,
+    hit_counts: BTreeMap<u32, u64>,
+    tracing: bool,
+    trace: Vec<TraceEntry<W, I>>,
+}
+
+/// Why the [`Debugger`] stopped running the program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// The instruction at the program counter failed
+    Fault(ExecutionError),
+    /// Executed the requested number of instructions
+    Stepped,
+    /// Hit the breakpoint with this index
+    Breakpoint(usize),
+    /// The program counter left the program
+    Halted,
+    /// Executed `max_cycles` instructions without halting
+    CycleLimit,
+}
+
+/// An executed instruction and the state it left the computer in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry<W = u32, I = Instruction> {
+    pub pc: u32,
+    pub instruction: I,
+    pub next_pc: u32,
+    pub registers: Registers<W>,
+}
+
+impl<W: Word, I: Display> Display for TraceEntry<W, I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:>4}: {:<28} {} pc={}",
+            self.pc,
+            self.instruction.to_string(),
+            self.registers,
+            self.next_pc
+        )
+    }
+}
+
+impl<W: Word, I: Operation<W>> Debugger<W, I> {
+    pub fn new(computer: Computer<W, I>, max_cycles: u64) -> Self {
+        Self {
+            computer,
+            breakpoints: Vec::new(),
+            max_cycles,
+            cycles: 0,
+            hit_counts: BTreeMap::new(),
+            tracing: false,
+            trace: Vec::new(),
+        }
+    }
+
+    pub fn computer(&self) -> &Computer<W, I> {
+        &self.computer
+    }
+
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    /// Adds a breakpoint and returns its index
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) -> usize {
+        self.breakpoints.push(breakpoint);
+        self.breakpoints.len() - 1
+    }
+
+    pub fn remove_breakpoint(&mut self, index: usize) -> Option<Breakpoint> {
+        (index < self.breakpoints.len()).then(|| self.breakpoints.remove(index))
+    }
+
+    /// Whether executed instructions are recorded, see [`Debugger::take_trace`]
+    pub fn set_tracing(&mut self, tracing: bool) {
+        self.tracing = tracing;
+    }
+
+    /// The instructions recorded since the last call, oldest first
+    pub fn take_trace(&mut self) -> Vec<TraceEntry<W, I>> {
+        std::mem::take(&mut self.trace)
+    }
+
+    /// How often the instruction at each program counter was executed, for those that were
+    pub fn hit_counts(&self) -> &BTreeMap<u32, u64> {
+        &self.hit_counts
+    }
+
+    /// Executes up to `count` instructions, stopping early at a breakpoint
+    pub fn step(&mut self, count: u64) -> Stop {
+        for _ in 0..count {
+            match self.step_once() {
+                Stop::Stepped => {}
+                stop => return stop,
+            }
+        }
+        Stop::Stepped
+    }
+
+    /// Executes instructions until a breakpoint is hit or the program halts
+    pub fn continue_execution(&mut self) -> Stop {
         loop {
-            if self.step().is_none() {
-                break
+            match self.step_once() {
+                Stop::Stepped => {}
+                stop => return stop,
+            }
+        }
+    }
+
+    fn step_once(&mut self) -> Stop {
+        if self.computer.current_instruction().is_none() {
+            return Stop::Halted;
+        }
+        if self.cycles >= self.max_cycles {
+            return Stop::CycleLimit;
+        }
+        let held_before: Vec<bool> = self
+            .breakpoints
+            .iter()
+            .map(|breakpoint| breakpoint.holds(&self.computer))
+            .collect();
+        let pc = self.computer.pc();
+        let instruction = match self.computer.step() {
+            Ok(instruction) => instruction.expect("checked above"),
+            Err(error) => return Stop::Fault(error),
+        };
+        self.cycles += 1;
+        *self.hit_counts.entry(pc).or_default() += 1;
+        if self.tracing {
+            self.trace.push(TraceEntry {
+                pc,
+                instruction,
+                next_pc: self.computer.pc(),
+                registers: self.computer.registers().clone(),
+            });
+        }
+
+        // register conditions only trigger when they become true, or continuing would stop
+        // right away for as long as they hold
+        let hit = self
+            .breakpoints
+            .iter()
+            .zip(held_before)
+            .position(|(breakpoint, held_before)| {
+                breakpoint.holds(&self.computer)
+                    && (matches!(breakpoint, Breakpoint::Pc(_)) || !held_before)
+            });
+        match hit {
+            Some(index) => Stop::Breakpoint(index),
+            None if self.computer.current_instruction().is_none() => Stop::Halted,
+            None => Stop::Stepped,
+        }
+    }
+}
+
+/// Where the [`Debugger`] stops: before the instruction at a program counter, or once a
+/// register condition becomes true. Written as `pc=7`, `a==1`, `b>100` and the like
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint {
+    Pc(u32),
+    Register(Register, Comparison, u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    /// In the order they have to be tried when parsing, longest first
+    const ALL: [(Comparison, &'static str); 6] = [
+        (Comparison::Equal, "=="),
+        (Comparison::NotEqual, "!="),
+        (Comparison::LessOrEqual, "<="),
+        (Comparison::GreaterOrEqual, ">="),
+        (Comparison::Less, "<"),
+        (Comparison::Greater, ">"),
+    ];
+
+    fn symbol(self) -> &'static str {
+        Self::ALL
+            .iter()
+            .find(|(comparison, _)| *comparison == self)
+            .map(|(_, symbol)| *symbol)
+            .unwrap()
+    }
+
+    fn compare<T: Ord>(self, lhs: &T, rhs: &T) -> bool {
+        match self {
+            Comparison::Equal => lhs == rhs,
+            Comparison::NotEqual => lhs != rhs,
+            Comparison::Less => lhs < rhs,
+            Comparison::LessOrEqual => lhs <= rhs,
+            Comparison::Greater => lhs > rhs,
+            Comparison::GreaterOrEqual => lhs >= rhs,
+        }
+    }
+}
+
+impl Breakpoint {
+    fn holds<W: Word, I: Operation<W>>(&self, computer: &Computer<W, I>) -> bool {
+        match *self {
+            Breakpoint::Pc(pc) => computer.pc() == pc,
+            Breakpoint::Register(register, comparison, value) => computer
+                .registers()
+                .get(register)
+                .is_ok_and(|register_value| {
+                    comparison.compare(register_value, &W::from_u32(value))
+                }),
+        }
+    }
+}
+
+impl Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Breakpoint::Pc(pc) => write!(f, "pc={}", pc),
+            Breakpoint::Register(register, comparison, value) => {
+                write!(f, "{}{}{}", register, comparison.symbol(), value)
             }
         }
     }
+}
+
+impl FromStr for Breakpoint {
+    type Err = parsing::ParseError;
 
-    fn step(&mut self) -> Option<Instruction> {
-        let instruction = self.current_instruction()?;
-        match instruction {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        let breakpoint = if parser.try_literal("pc") {
+            parser.expect_literal("=")?;
+            Breakpoint::Pc(parser.parse_number()?)
+        } else {
+            let register = parser
+                .next_char()
+                .and_then(Register::from_name)
+                .ok_or_else(|| parser.error_at(0, "expected pc or a register"))?;
+            let comparison = Comparison::ALL
+                .iter()
+                .find(|(_, symbol)| parser.try_literal(symbol))
+                .map(|(comparison, _)| *comparison)
+                .ok_or_else(|| parser.error("expected a comparison"))?;
+            Breakpoint::Register(register, comparison, parser.parse_number()?)
+        };
+        parser.expect_end()?;
+        Ok(breakpoint)
+    }
+}
+
+/// A register by index, named by a letter: `a` is 0, `b` is 1 and so on
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Register(u8);
+
+impl Register {
+    pub const A: Register = Register(0);
+    pub const B: Register = Register(1);
+    /// How many registers there are letters for
+    pub const MAX_COUNT: usize = 26;
+
+    pub fn from_name(name: char) -> Option<Self> {
+        name.is_ascii_lowercase()
+            .then(|| Register(name as u8 - b'a'))
+    }
+
+    pub fn index(self) -> usize {
+        self.0.into()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Instruction {
+    Half(Register),
+    Triple(Register),
+    Increment(Register),
+    Jump(i32),
+    JumpIfEven(Register, i32),
+    JumpIfOne(Register, i32),
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", (b'a' + self.0) as char)
+    }
+}
+
+impl Instruction {
+    /// The program counter this instruction jumps to from `pc`, if it's a jump. The target can be
+    /// outside the program, which halts it
+    pub fn jump_target(self, pc: u32) -> Option<i64> {
+        match self {
+            Instruction::Jump(offset)
+            | Instruction::JumpIfEven(_, offset)
+            | Instruction::JumpIfOne(_, offset) => Some(i64::from(pc) + i64::from(offset)),
+            Instruction::Half(_) | Instruction::Triple(_) | Instruction::Increment(_) => None,
+        }
+    }
+}
+
+/// The puzzle syntax, e.g. `hlf a` or `jie a, +4`
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::Half(register) => write!(f, "hlf {}", register),
+            Instruction::Triple(register) => write!(f, "tpl {}", register),
+            Instruction::Increment(register) => write!(f, "inc {}", register),
+            Instruction::Jump(offset) => write!(f, "jmp {:+}", offset),
+            Instruction::JumpIfEven(register, offset) => {
+                write!(f, "jie {}, {:+}", register, offset)
+            }
+            Instruction::JumpIfOne(register, offset) => write!(f, "jio {}, {:+}", register, offset),
+        }
+    }
+}
+
+impl<W: Word> Operation<W> for Instruction {
+    fn execute(&self, registers: &mut Registers<W>) -> Result<Flow<W>, ExecutionError> {
+        Ok(match *self {
             Instruction::Half(register) => {
-                *self.register_mut(register) /= 2;
-                self.pc += 1;
+                registers.update(register, |value| Some(value.half()))?;
+                Flow::Next
             }
             Instruction::Triple(register) => {
-                *self.register_mut(register) *= 3;
-                self.pc += 1;
+                registers.update(register, |value| value.checked_mul(&W::from_u32(3)))?;
+                Flow::Next
             }
             Instruction::Increment(register) => {
-                *self.register_mut(register) += 1;
-                self.pc += 1;
-            }
-            Instruction::Jump(offset) => {
-                self.jump(offset);
+                registers.update(register, |value| value.checked_add(&W::from_u32(1)))?;
+                Flow::Next
             }
+            Instruction::Jump(offset) => Flow::Jump(offset.into()),
             Instruction::JumpIfEven(register, offset) => {
-                if self.register(register) % 2 == 0 {
-                    self.jump(offset);
+                if registers.get(register)?.is_even() {
+                    Flow::Jump(offset.into())
                 } else {
-                    self.pc += 1;
+                    Flow::Next
                 }
             }
             Instruction::JumpIfOne(register, offset) => {
-                if self.register(register) == 1 {
-                    self.jump(offset);
+                if *registers.get(register)? == W::from_u32(1) {
+                    Flow::Jump(offset.into())
                 } else {
-                    self.pc += 1;
+                    Flow::Next
                 }
             }
-        }
-        Some(instruction)
+        })
     }
 
-    fn current_instruction(&self) -> Option<Instruction> {
-        self.instructions.get(self.pc as usize).copied()
+    /// Recognizes the loop of the puzzle input, which counts in `y` how many steps of the Collatz
+    /// sequence it takes `x` to reach 1, and computes that directly:
+    ///
+    /// ```text
+    /// jio x, +8
+    /// inc y
+    /// jie x, +4
+    /// tpl x
+    /// inc x
+    /// jmp +2
+    /// hlf x
+    /// jmp -7
+    /// ```
+    fn fast_forward(
+        program: &[Self],
+        head: usize,
+        registers: &mut Registers<W>,
+    ) -> Result<Option<i64>, ExecutionError> {
+        use Instruction::{Half, Increment, Jump, JumpIfEven, JumpIfOne, Triple};
+        let window = program.get(head..head + 8);
+        let Some(&[JumpIfOne(x, 8), Increment(y), ..]) = window else {
+            return Ok(None);
+        };
+        let collatz_loop = [
+            JumpIfOne(x, 8),
+            Increment(y),
+            JumpIfEven(x, 4),
+            Triple(x),
+            Increment(x),
+            Jump(2),
+            Half(x),
+            Jump(-7),
+        ];
+        if x == y || window != Some(&collatz_loop[..]) {
+            return Ok(None);
+        }
+        let one = W::from_u32(1);
+        let mut value = registers.get(x)?.clone();
+        if value == W::from_u32(0) {
+            // halving zero keeps it at zero
+            return Err(ExecutionError::InfiniteLoop);
+        }
+        let mut steps = registers.get(y)?.clone();
+        while value != one {
+            steps = steps.checked_add(&one).ok_or(ExecutionError::Overflow)?;
+            value = if value.is_even() {
+                value.half()
+            } else {
+                value
+                    .checked_mul(&W::from_u32(3))
+                    .and_then(|tripled| tripled.checked_add(&one))
+                    .ok_or(ExecutionError::Overflow)?
+            };
+        }
+        registers.set(x, value)?;
+        registers.set(y, steps)?;
+        Ok(Some(8))
     }
+}
 
-    fn register(&self, register: Register) -> u32 {
-        match register {
-            Register::A => self.a,
-            Register::B => self.b,
+/// A listing of the program with the program counter of every instruction and where the jumps go
+pub fn disassemble(instructions: &[Instruction]) -> String {
+    let mut listing = String::new();
+    for (pc, instruction) in (0..).zip(instructions) {
+        let line = format!("{:>4}: {}", pc, instruction);
+        match instruction.jump_target(pc) {
+            Some(target) if (0..instructions.len() as i64).contains(&target) => {
+                listing += &format!("{:<20} ; -> {}\n", line, target)
+            }
+            Some(target) => listing += &format!("{:<20} ; -> {} (halt)\n", line, target),
+            None => listing += &format!("{}\n", line),
         }
     }
+    listing
+}
 
-    fn register_mut(&mut self, register: Register) -> &mut u32 {
-        match register {
-            Register::A => &mut self.a,
-            Register::B => &mut self.b,
+/// The compact binary form of a program, see [`decode`]. Each instruction is a byte with the
+/// opcode in the upper three bits and the register index in the lower five, followed by the offset
+/// for jumps as a zigzag LEB128 varint
+pub fn encode(instructions: &[Instruction]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for &instruction in instructions {
+        let (opcode, register, offset) = match instruction {
+            Instruction::Half(register) => (0, register, None),
+            Instruction::Triple(register) => (1, register, None),
+            Instruction::Increment(register) => (2, register, None),
+            Instruction::Jump(offset) => (3, Register::A, Some(offset)),
+            Instruction::JumpIfEven(register, offset) => (4, register, Some(offset)),
+            Instruction::JumpIfOne(register, offset) => (5, register, Some(offset)),
+        };
+        bytes.push(opcode << 5 | register.0);
+        if let Some(offset) = offset {
+            let mut zigzag = ((offset << 1) ^ (offset >> 31)) as u32;
+            while zigzag >= 0x80 {
+                bytes.push(zigzag as u8 | 0x80);
+                zigzag >>= 7;
+            }
+            bytes.push(zigzag as u8);
         }
     }
+    bytes
+}
 
-    fn jump(&mut self, offset: i32) {
-        self.pc = self.pc.checked_add_signed(offset).unwrap_or(u32::MAX);
-    }
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    UnknownOpcode { offset: usize, byte: u8 },
+    InvalidJumpOffset { offset: usize },
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Register {
-    A,
-    B,
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::UnknownOpcode { offset, byte } => {
+                write!(f, "unknown opcode {:#04x} at byte {}", byte, offset)
+            }
+            DecodeError::InvalidJumpOffset { offset } => {
+                write!(f, "truncated or oversized jump offset at byte {}", offset)
+            }
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Instruction {
-    Half(Register),
-    Triple(Register),
-    Increment(Register),
-    Jump(i32),
-    JumpIfEven(Register, i32),
-    JumpIfOne(Register, i32),
+/// Reads a program written by [`encode`]
+pub fn decode(bytes: &[u8]) -> Result<Vec<Instruction>, DecodeError> {
+    let mut instructions = Vec::new();
+    let mut position = 0;
+    while let Some(&byte) = bytes.get(position) {
+        let register = Register(byte & 0x1f);
+        let start = position;
+        position += 1;
+        let mut offset = || {
+            let mut zigzag: u32 = 0;
+            for shift in (0..32).step_by(7) {
+                let &byte = bytes
+                    .get(position)
+                    .ok_or(DecodeError::InvalidJumpOffset { offset: start })?;
+                position += 1;
+                let bits = u32::from(byte & 0x7f);
+                // the last byte only has room for the top four bits
+                if bits.leading_zeros() < shift {
+                    break;
+                }
+                zigzag |= bits << shift;
+                if byte & 0x80 == 0 {
+                    return Ok((zigzag >> 1) as i32 ^ -((zigzag & 1) as i32));
+                }
+            }
+            Err(DecodeError::InvalidJumpOffset { offset: start })
+        };
+        if register.index() >= Register::MAX_COUNT {
+            return Err(DecodeError::UnknownOpcode {
+                offset: start,
+                byte,
+            });
+        }
+        instructions.push(match byte >> 5 {
+            0 => Instruction::Half(register),
+            1 => Instruction::Triple(register),
+            2 => Instruction::Increment(register),
+            3 if register == Register::A => Instruction::Jump(offset()?),
+            4 => Instruction::JumpIfEven(register, offset()?),
+            5 => Instruction::JumpIfOne(register, offset()?),
+            _ => {
+                return Err(DecodeError::UnknownOpcode {
+                    offset: start,
+                    byte,
+                })
+            }
+        });
+    }
+    Ok(instructions)
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct ParseError;
+pub struct ParseError;
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid instruction")
+    }
+}
+
+impl LineError for ParseError {}
 
 impl FromStr for Instruction {
     type Err = ParseError;
@@ -168,9 +1042,9 @@ impl FromStr for Instruction {
 }
 
 fn parse_register(s: &str) -> Result<Register, ParseError> {
-    match s {
-        "a" => Ok(Register::A),
-        "b" => Ok(Register::B),
+    let mut chars = s.chars();
+    match (chars.next().and_then(Register::from_name), chars.next()) {
+        (Some(register), None) => Ok(register),
         _ => Err(ParseError),
     }
 }
@@ -182,10 +1056,229 @@ fn parse_offset(mut s: &str) -> Result<i32, ParseError> {
     s.parse::<i32>().map_err(|_| ParseError)
 }
 
+/// What an [`Assembunny`] instruction reads or writes: a register or a constant
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Operand {
+    Register(Register),
+    Constant(i64),
+}
+
+impl Operand {
+    fn value<W: Word>(self, registers: &Registers<W>) -> Result<W, ExecutionError> {
+        match self {
+            Operand::Register(register) => registers.get(register).cloned(),
+            Operand::Constant(value) => W::from_i64(value).ok_or(ExecutionError::Overflow),
+        }
+    }
+
+    /// The value as the offset of a jump or toggle
+    fn offset<W: Word>(self, registers: &Registers<W>) -> Result<i64, ExecutionError> {
+        match self {
+            Operand::Register(register) => registers
+                .get(register)?
+                .to_i64()
+                .ok_or(ExecutionError::Overflow),
+            Operand::Constant(value) => Ok(value),
+        }
+    }
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Register(register) => write!(f, "{}", register),
+            Operand::Constant(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl FromStr for Operand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_register(s)
+            .map(Operand::Register)
+            .or_else(|_| s.parse().map(Operand::Constant).map_err(|_| ParseError))
+    }
+}
+
+/// The assembunny instruction set from Advent of Code 2016, e.g. `cpy 41 a` or `jnz a -2`, to run
+/// those programs on the same [`Computer`]. Instructions that `tgl` makes invalid, like copying to
+/// a constant, are skipped
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Assembunny {
+    Copy(Operand, Operand),
+    Increment(Operand),
+    Decrement(Operand),
+    JumpIfNotZero(Operand, Operand),
+    Toggle(Operand),
+    Out(Operand),
+}
+
+impl<W: Word> Operation<W> for Assembunny {
+    fn execute(&self, registers: &mut Registers<W>) -> Result<Flow<W>, ExecutionError> {
+        Ok(match *self {
+            Assembunny::Copy(source, Operand::Register(target)) => {
+                let value = source.value(registers)?;
+                registers.set(target, value)?;
+                Flow::Next
+            }
+            Assembunny::Increment(Operand::Register(register)) => {
+                registers.update(register, |value| value.checked_add(&W::from_u32(1)))?;
+                Flow::Next
+            }
+            Assembunny::Decrement(Operand::Register(register)) => {
+                registers.update(register, |value| value.checked_sub(&W::from_u32(1)))?;
+                Flow::Next
+            }
+            Assembunny::JumpIfNotZero(condition, offset) => {
+                if condition.value(registers)? != W::from_u32(0) {
+                    Flow::Jump(offset.offset(registers)?)
+                } else {
+                    Flow::Next
+                }
+            }
+            Assembunny::Toggle(offset) => Flow::Toggle(offset.offset(registers)?),
+            Assembunny::Out(value) => Flow::Output(value.value(registers)?),
+            Assembunny::Copy(_, Operand::Constant(_))
+            | Assembunny::Increment(Operand::Constant(_))
+            | Assembunny::Decrement(Operand::Constant(_)) => Flow::Next,
+        })
+    }
+
+    fn toggled(&self) -> Self {
+        match *self {
+            Assembunny::Increment(operand) => Assembunny::Decrement(operand),
+            Assembunny::Decrement(operand)
+            | Assembunny::Toggle(operand)
+            | Assembunny::Out(operand) => Assembunny::Increment(operand),
+            Assembunny::Copy(lhs, rhs) => Assembunny::JumpIfNotZero(lhs, rhs),
+            Assembunny::JumpIfNotZero(lhs, rhs) => Assembunny::Copy(lhs, rhs),
+        }
+    }
+
+    /// Recognizes loops that add a register to another, or the product of two registers:
+    ///
+    /// ```text
+    /// inc x        cpy s c
+    /// dec y        inc x
+    /// jnz y -2     dec c
+    ///              jnz c -2
+    ///              dec d
+    ///              jnz d -5
+    /// ```
+    ///
+    /// The `inc` and `dec` of the addition can also be the other way around
+    fn fast_forward(
+        program: &[Self],
+        head: usize,
+        registers: &mut Registers<W>,
+    ) -> Result<Option<i64>, ExecutionError> {
+        use Assembunny::{Decrement, Increment, JumpIfNotZero};
+        use Operand::{Constant, Register as Reg};
+        let zero = W::from_u32(0);
+
+        let window = program.get(head..head + 6);
+        if let Some(
+            &[Assembunny::Copy(source, Reg(c)), Increment(Reg(x)), .., Decrement(Reg(d)), _],
+        ) = window
+        {
+            let multiplication_loop = [
+                Assembunny::Copy(source, Reg(c)),
+                Increment(Reg(x)),
+                Decrement(Reg(c)),
+                JumpIfNotZero(Reg(c), Constant(-2)),
+                Decrement(Reg(d)),
+                JumpIfNotZero(Reg(d), Constant(-5)),
+            ];
+            let distinct = x != c && x != d && c != d;
+            let source_is_read_only = ![x, c, d].map(Reg).contains(&source);
+            if distinct && source_is_read_only && window == Some(&multiplication_loop[..]) {
+                let factor = source.value(registers)?;
+                let count = registers.get(d)?.clone();
+                // with a negative count the loop only ends by overflowing, so step through it
+                if factor <= zero || count <= zero {
+                    return Ok(None);
+                }
+                let product = factor.checked_mul(&count).ok_or(ExecutionError::Overflow)?;
+                registers.update(x, |value| value.checked_add(&product))?;
+                registers.set(c, zero.clone())?;
+                registers.set(d, zero)?;
+                return Ok(Some(6));
+            }
+        }
+
+        if let Some(&[first, second, JumpIfNotZero(Reg(y), Constant(-2))]) =
+            program.get(head..head + 3)
+        {
+            let x = match (first, second) {
+                (Increment(Reg(x)), Decrement(Reg(y2)))
+                | (Decrement(Reg(y2)), Increment(Reg(x)))
+                    if y2 == y && x != y =>
+                {
+                    x
+                }
+                _ => return Ok(None),
+            };
+            let count = registers.get(y)?.clone();
+            if count <= zero {
+                return Ok(None);
+            }
+            registers.update(x, |value| value.checked_add(&count))?;
+            registers.set(y, zero)?;
+            return Ok(Some(3));
+        }
+        Ok(None)
+    }
+}
+
+impl Display for Assembunny {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Assembunny::Copy(source, target) => write!(f, "cpy {} {}", source, target),
+            Assembunny::Increment(register) => write!(f, "inc {}", register),
+            Assembunny::Decrement(register) => write!(f, "dec {}", register),
+            Assembunny::JumpIfNotZero(condition, offset) => {
+                write!(f, "jnz {} {}", condition, offset)
+            }
+            Assembunny::Toggle(offset) => write!(f, "tgl {}", offset),
+            Assembunny::Out(value) => write!(f, "out {}", value),
+        }
+    }
+}
+
+impl FromStr for Assembunny {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let instruction = words.next().ok_or(ParseError)?;
+        let operands = words.map(str::parse).collect::<Result<Vec<Operand>, _>>()?;
+        Ok(match (instruction, operands.as_slice()) {
+            ("cpy", &[source, target]) => Assembunny::Copy(source, target),
+            ("inc", &[register @ Operand::Register(_)]) => Assembunny::Increment(register),
+            ("dec", &[register @ Operand::Register(_)]) => Assembunny::Decrement(register),
+            ("jnz", &[condition, offset]) => Assembunny::JumpIfNotZero(condition, offset),
+            ("tgl", &[offset]) => Assembunny::Toggle(offset),
+            ("out", &[value]) => Assembunny::Out(value),
+            _ => return Err(ParseError),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn state(computer: &Computer) -> (u32, u32, u32) {
+        let registers = computer.registers();
+        (
+            *registers.get(Register::A).unwrap(),
+            *registers.get(Register::B).unwrap(),
+            computer.pc(),
+        )
+    }
+
     #[test]
     fn test_parsing() {
         assert_eq!("hlf a".parse(), Ok(Instruction::Half(Register::A)));
@@ -204,7 +1297,7 @@ mod tests {
 
     #[test]
     fn test_compute() {
-        let mut c = Computer::with_instructions(
+        let mut c: Computer = Computer::with_instructions(
             [
                 "inc a",
                 "tpl a",
@@ -218,36 +1311,319 @@ mod tests {
             ]
             .map(|s| s.parse::<Instruction>().unwrap()),
         );
-        assert_eq!((c.a, c.b, c.pc), (0, 0, 0));
-
-        assert_eq!(c.step(), Some(Instruction::Increment(Register::A)));
-        assert_eq!((c.a, c.b, c.pc), (1, 0, 1));
-
-        assert_eq!(c.step(), Some(Instruction::Triple(Register::A)));
-        assert_eq!((c.a, c.b, c.pc), (3, 0, 2));
-
-        assert_eq!(c.step(), Some(Instruction::Half(Register::A)));
-        assert_eq!((c.a, c.b, c.pc), (1, 0, 3));
-
-        assert_eq!(c.step(), Some(Instruction::Increment(Register::B)));
-        assert_eq!((c.a, c.b, c.pc), (1, 1, 4));
-        assert_eq!(c.step(), Some(Instruction::JumpIfOne(Register::B, -1)));
-        assert_eq!((c.a, c.b, c.pc), (1, 1, 3));
-        assert_eq!(c.step(), Some(Instruction::Increment(Register::B)));
-        assert_eq!((c.a, c.b, c.pc), (1, 2, 4));
-        assert_eq!(c.step(), Some(Instruction::JumpIfOne(Register::B, -1)));
-        assert_eq!((c.a, c.b, c.pc), (1, 2, 5));
-
-        assert_eq!(c.step(), Some(Instruction::JumpIfEven(Register::A, 3)));
-        assert_eq!((c.a, c.b, c.pc), (1, 2, 6));
-        assert_eq!(c.step(), Some(Instruction::Increment(Register::A)));
-        assert_eq!((c.a, c.b, c.pc), (2, 2, 7));
-        assert_eq!(c.step(), Some(Instruction::Jump(-2)));
-        assert_eq!((c.a, c.b, c.pc), (2, 2, 5));
-        assert_eq!(c.step(), Some(Instruction::JumpIfEven(Register::A, 3)));
-        assert_eq!((c.a, c.b, c.pc), (2, 2, 8));
-
-        assert_eq!(c.step(), Some(Instruction::Jump(-100)));
-        assert_eq!(c.step(), None)
+        assert_eq!(state(&c), (0, 0, 0));
+
+        assert_eq!(c.step(), Ok(Some(Instruction::Increment(Register::A))));
+        assert_eq!(state(&c), (1, 0, 1));
+
+        assert_eq!(c.step(), Ok(Some(Instruction::Triple(Register::A))));
+        assert_eq!(state(&c), (3, 0, 2));
+
+        assert_eq!(c.step(), Ok(Some(Instruction::Half(Register::A))));
+        assert_eq!(state(&c), (1, 0, 3));
+
+        assert_eq!(c.step(), Ok(Some(Instruction::Increment(Register::B))));
+        assert_eq!(state(&c), (1, 1, 4));
+        assert_eq!(c.step(), Ok(Some(Instruction::JumpIfOne(Register::B, -1))));
+        assert_eq!(state(&c), (1, 1, 3));
+        assert_eq!(c.step(), Ok(Some(Instruction::Increment(Register::B))));
+        assert_eq!(state(&c), (1, 2, 4));
+        assert_eq!(c.step(), Ok(Some(Instruction::JumpIfOne(Register::B, -1))));
+        assert_eq!(state(&c), (1, 2, 5));
+
+        assert_eq!(c.step(), Ok(Some(Instruction::JumpIfEven(Register::A, 3))));
+        assert_eq!(state(&c), (1, 2, 6));
+        assert_eq!(c.step(), Ok(Some(Instruction::Increment(Register::A))));
+        assert_eq!(state(&c), (2, 2, 7));
+        assert_eq!(c.step(), Ok(Some(Instruction::Jump(-2))));
+        assert_eq!(state(&c), (2, 2, 5));
+        assert_eq!(c.step(), Ok(Some(Instruction::JumpIfEven(Register::A, 3))));
+        assert_eq!(state(&c), (2, 2, 8));
+
+        assert_eq!(c.step(), Ok(Some(Instruction::Jump(-100))));
+        assert_eq!(c.step(), Ok(None))
+    }
+
+    #[test]
+    fn test_breakpoint_parsing() {
+        assert_eq!("pc=7".parse(), Ok(Breakpoint::Pc(7)));
+        assert_eq!(
+            "a==1".parse(),
+            Ok(Breakpoint::Register(Register::A, Comparison::Equal, 1))
+        );
+        assert_eq!(
+            "b>=100".parse(),
+            Ok(Breakpoint::Register(
+                Register::B,
+                Comparison::GreaterOrEqual,
+                100
+            ))
+        );
+        assert_eq!(
+            "b<3".parse::<Breakpoint>().unwrap().to_string(),
+            "b<3".to_owned()
+        );
+        assert!("pc>7".parse::<Breakpoint>().is_err());
+        assert_eq!(
+            "c==1".parse(),
+            Ok(Breakpoint::Register(Register(2), Comparison::Equal, 1))
+        );
+        assert!("C==1".parse::<Breakpoint>().is_err());
+        assert!("a=1".parse::<Breakpoint>().is_err());
+        assert!("a==1 ".parse::<Breakpoint>().is_err());
+    }
+
+    #[test]
+    fn test_debugger() {
+        let program = ["inc a", "inc b", "jie b, -2", "jmp -3", "hlf a"]
+            .map(|s| s.parse::<Instruction>().unwrap());
+        let mut debugger = Debugger::new(Computer::with_instructions(program), 100);
+        debugger.set_tracing(true);
+        assert_eq!(debugger.add_breakpoint("pc=3".parse().unwrap()), 0);
+        assert_eq!(debugger.add_breakpoint("a>=2".parse().unwrap()), 1);
+
+        assert_eq!(debugger.step(2), Stop::Stepped);
+        assert_eq!(state(debugger.computer()), (1, 1, 2));
+        assert_eq!(debugger.continue_execution(), Stop::Breakpoint(0));
+        assert_eq!(debugger.computer().pc(), 3);
+        let trace = debugger.take_trace();
+        assert_eq!(trace.len(), 3);
+        assert_eq!(
+            trace[2],
+            TraceEntry {
+                pc: 2,
+                instruction: Instruction::JumpIfEven(Register::B, -2),
+                next_pc: 3,
+                registers: Registers { values: vec![1, 1] },
+            }
+        );
+
+        // a>=2 triggers when a becomes 2, but not again while it stays that way
+        assert_eq!(debugger.continue_execution(), Stop::Breakpoint(1));
+        assert_eq!(debugger.computer().pc(), 1);
+        assert_eq!(debugger.remove_breakpoint(0), Some(Breakpoint::Pc(3)));
+        assert_eq!(debugger.continue_execution(), Stop::CycleLimit);
+        assert_eq!(debugger.cycles(), 100);
+        assert_eq!(debugger.hit_counts().values().sum::<u64>(), 100);
+        assert_eq!(debugger.hit_counts().get(&4), None);
+
+        let mut debugger: Debugger =
+            Debugger::new(Computer::with_instructions(["hlf a".parse().unwrap()]), 5);
+        assert_eq!(debugger.step(3), Stop::Halted);
+        assert_eq!(debugger.continue_execution(), Stop::Halted);
+        assert_eq!(debugger.cycles(), 1);
+    }
+
+    const PROGRAM: &str = "\
+jio a, +4
+inc a
+tpl b
+jmp +2
+hlf a
+jie b, -5
+jmp +300
+jio b, -70000";
+
+    #[test]
+    fn test_display_round_trip() {
+        let instructions: Vec<Instruction> = parse_lines(PROGRAM).unwrap();
+        let printed: Vec<String> = instructions.iter().map(ToString::to_string).collect();
+        assert_eq!(printed.join("\n"), PROGRAM);
+        assert_eq!(
+            "jmp 3".parse::<Instruction>().unwrap().to_string(),
+            "jmp +3"
+        );
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let instructions: Vec<Instruction> = parse_lines(PROGRAM).unwrap();
+        assert_eq!(
+            disassemble(&instructions[..4]),
+            "   0: jio a, +4      ; -> 4 (halt)\n   1: inc a\n   2: tpl b\n   3: jmp +2         ; -> 5 (halt)\n"
+        );
+        assert_eq!(
+            disassemble(&instructions).lines().nth(5),
+            Some("   5: jie b, -5      ; -> 0")
+        );
+    }
+
+    #[test]
+    fn test_binary_encoding() {
+        let instructions: Vec<Instruction> = parse_lines(PROGRAM).unwrap();
+        let bytes = encode(&instructions);
+        assert_eq!(&bytes[..3], [0xa0, 8, 0x40]);
+        // one byte per instruction, plus one per 7 bits of zigzagged jump offset
+        assert_eq!(bytes.len(), 8 + 1 + 1 + 1 + 2 + 3);
+        assert_eq!(decode(&bytes), Ok(instructions));
+
+        assert_eq!(
+            decode(&[0x04, 0xff]),
+            Err(DecodeError::UnknownOpcode {
+                offset: 1,
+                byte: 0xff
+            })
+        );
+        assert_eq!(
+            decode(&[0x1f]),
+            Err(DecodeError::UnknownOpcode {
+                offset: 0,
+                byte: 0x1f
+            })
+        );
+        assert_eq!(
+            decode(&[0x60, 0x80]),
+            Err(DecodeError::InvalidJumpOffset { offset: 0 })
+        );
+
+        let extremes = [Instruction::Jump(i32::MIN), Instruction::Jump(i32::MAX)];
+        let bytes = encode(&extremes);
+        assert_eq!(bytes.len(), 2 * 6);
+        assert_eq!(decode(&bytes), Ok(extremes.to_vec()));
+        // a sixth byte, and bits past the 32nd, would be silently dropped
+        assert_eq!(
+            decode(&[0x60, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00]),
+            Err(DecodeError::InvalidJumpOffset { offset: 0 })
+        );
+        assert_eq!(
+            decode(&[0x60, 0xff, 0xff, 0xff, 0xff, 0x1f]),
+            Err(DecodeError::InvalidJumpOffset { offset: 0 })
+        );
+        assert_eq!(
+            decode(&[0x60, 0x80, 0x80, 0x80, 0x80, 0x10]),
+            Err(DecodeError::InvalidJumpOffset { offset: 0 })
+        );
+    }
+
+    #[test]
+    fn test_register_widths() {
+        let program = ["tpl a"; 30].map(|s| s.parse::<Instruction>().unwrap());
+
+        let mut computer: Computer<u32> = Computer::with_instructions(program);
+        computer.registers_mut().set(Register::A, 1u32).unwrap();
+        assert_eq!(computer.run(), Err(ExecutionError::Overflow));
+        // 3^20 is the largest power of three below 2^32
+        assert_eq!(computer.pc(), 20);
+        assert_eq!(computer.registers().get(Register::A), Ok(&3u32.pow(20)));
+
+        let mut computer: Computer<u64> = Computer::with_instructions(program);
+        computer.registers_mut().set(Register::A, 1u32).unwrap();
+        assert_eq!(computer.run(), Ok(()));
+        assert_eq!(computer.registers().get(Register::A), Ok(&3u64.pow(30)));
+
+        let mut computer: Computer<BigUint> = Computer::with_instructions(program);
+        computer.load([program; 3].concat());
+        computer.registers_mut().set(Register::A, 1u32).unwrap();
+        assert_eq!(computer.run(), Ok(()));
+        assert_eq!(
+            computer.registers().get(Register::A),
+            Ok(&BigUint::from(3u32).pow(90))
+        );
+
+        let mut computer: Computer = Computer::with_instructions(["inc c".parse().unwrap()]);
+        assert_eq!(
+            computer.run(),
+            Err(ExecutionError::UnknownRegister(Register(2)))
+        );
+        let mut computer: Computer = Computer::with_register_count(3)
+            .unwrap()
+            .with_program(["inc c".parse().unwrap()]);
+        assert_eq!(computer.run(), Ok(()));
+        assert_eq!(computer.registers().to_string(), "a=0 b=0 c=1");
+
+        assert!(Computer::<u32>::with_register_count(Register::MAX_COUNT).is_ok());
+        assert_eq!(
+            Computer::<u32>::with_register_count(27).unwrap_err(),
+            TooManyRegisters(27)
+        );
+    }
+
+    #[test]
+    fn test_assembunny() {
+        let run = |program: &str| {
+            let mut computer: Computer<u32, Assembunny> = Computer::with_register_count(4)
+                .unwrap()
+                .with_program(parse_lines(program).unwrap());
+            computer.run().map(|()| computer)
+        };
+
+        let computer = run("cpy 41 a\ninc a\ninc a\ndec a\njnz a 2\ndec a").unwrap();
+        assert_eq!(computer.registers().get(Register::A), Ok(&42));
+
+        let computer = run("cpy 2 a\ntgl a\ntgl a\ntgl a\ncpy 1 a\ndec a\ndec a").unwrap();
+        assert_eq!(computer.registers().get(Register::A), Ok(&3));
+        assert_eq!(computer.instructions()[3].to_string(), "inc a");
+
+        let computer = run("cpy 3 b\nout b\ndec b\njnz b -2").unwrap();
+        assert_eq!(computer.output(), [3, 2, 1]);
+
+        assert_eq!(run("dec d").unwrap_err(), ExecutionError::Overflow);
+        assert_eq!(run("cpy -1 a").unwrap_err(), ExecutionError::Overflow);
+
+        // jumps back through a register holding a negative offset
+        let program = "cpy 3 a\ndec b\ndec a\ncpy -3 c\njnz a c";
+        let mut computer: Computer<i64, Assembunny> = Computer::with_register_count(4)
+            .unwrap()
+            .with_program(parse_lines(program).unwrap());
+        assert_eq!(computer.run(), Ok(()));
+        assert_eq!(computer.registers().to_string(), "a=0 b=-3 c=-3 d=0");
+        assert!("jnz 1".parse::<Assembunny>().is_err());
+        assert!("inc 1".parse::<Assembunny>().is_err());
+    }
+
+    #[test]
+    fn test_loops() {
+        let collatz = [
+            "jio a, +8",
+            "inc b",
+            "jie a, +4",
+            "tpl a",
+            "inc a",
+            "jmp +2",
+            "hlf a",
+            "jmp -7",
+        ]
+        .map(|s| s.parse::<Instruction>().unwrap());
+        for fast_forward in [false, true] {
+            let mut computer: Computer = Computer::with_instructions(collatz);
+            computer.set_fast_forward(fast_forward);
+            computer.registers_mut().set(Register::A, 27u32).unwrap();
+            assert_eq!(computer.run(), Ok(()));
+            assert_eq!(state(&computer), (1, 111, 8));
+        }
+        let mut computer: Computer = Computer::with_instructions(collatz);
+        assert_eq!(computer.run(), Err(ExecutionError::InfiniteLoop));
+
+        let mut computer: Computer = Computer::with_instructions(["jmp +0".parse().unwrap()]);
+        assert_eq!(computer.run(), Err(ExecutionError::InfiniteLoop));
+        let mut computer: Computer =
+            Computer::with_instructions(parse_lines("jio a, +2\njmp -1").unwrap());
+        assert_eq!(computer.run(), Err(ExecutionError::InfiniteLoop));
+        assert_eq!(computer.pc(), 0);
+        // counts a down from 3 over and over, after a long count down of b
+        let mut computer: Computer<u32, Assembunny> =
+            Computer::with_register_count(2).unwrap().with_program(
+                parse_lines(
+                    "cpy 1000 b\ndec b\njnz b -1\ncpy 3 a\ndec a\njnz a 2\ncpy 3 a\njnz 1 -3",
+                )
+                .unwrap(),
+            );
+        computer.set_fast_forward(false);
+        assert_eq!(computer.run(), Err(ExecutionError::InfiniteLoop));
+        assert_eq!(computer.registers().get(Register::B), Ok(&0));
+
+        // 3 * 10^10 instructions when stepped through
+        let mut computer: Computer<u64, Assembunny> =
+            Computer::with_register_count(4).unwrap().with_program(
+                parse_lines(
+                    "cpy 100000 b\ncpy 100000 d\ncpy b c\ninc a\ndec c\njnz c -2\ndec d\njnz d -5",
+                )
+                .unwrap(),
+            );
+        assert_eq!(computer.run(), Ok(()));
+        assert_eq!(
+            computer.registers().to_string(),
+            "a=10000000000 b=100000 c=0 d=0"
+        );
     }
 }
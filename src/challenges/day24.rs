@@ -1,9 +1,33 @@
 use itertools::Itertools;
 
-use super::Challenge;
+use super::{parse_lines, Challenge, ChallengeError, ChallengeResult};
+use crate::params::{day_params, Params};
 
 pub struct Day24 {
     package_weights: Vec<u64>,
+    params: Day24Params,
+}
+
+day_params! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Day24Params {
+        /// How many groups of equal weight the packages are split into in part 1
+        part1_groups: u64 = 3,
+        /// How many groups of equal weight the packages are split into in part 2
+        part2_groups: u64 = 4,
+    }
+}
+
+impl Day24 {
+    fn optimal_quantum_entanglement(&self, num_groups: u64) -> ChallengeResult<u64> {
+        let distributor = PackageDistributor::new(&self.package_weights, num_groups)
+            .map_err(ChallengeError::new)?;
+        Ok(quantum_entanglement(
+            &distributor
+                .optimal_group_one()
+                .ok_or_else(|| ChallengeError::new("no valid group one found"))?,
+        ))
+    }
 }
 
 impl Challenge for Day24 {
@@ -12,57 +36,53 @@ impl Challenge for Day24 {
     type Part1Solution = u64;
     type Part2Solution = u64;
 
-    fn new(input: &str) -> Self {
-        Self {
-            package_weights: input
-                .lines()
-                .map(|line| line.parse::<u64>().unwrap())
-                .collect(),
-        }
+    const PARAMS: &'static [&'static str] = Day24Params::NAMES;
+
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Self::with_params(input, &Params::default())
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
-        let distributor = PackageDistributor::new(&self.package_weights, 3);
-        quantum_entanglement(
-            &distributor
-                .optimal_group_one()
-                .expect("no valid group one found"),
-        )
+    fn with_params(input: &str, params: &Params) -> ChallengeResult<Self> {
+        Ok(Self {
+            package_weights: parse_lines(input)?,
+            params: Day24Params::from_params(params)?,
+        })
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
-        let distributor = PackageDistributor::new(&self.package_weights, 4);
-        quantum_entanglement(
-            &distributor
-                .optimal_group_one()
-                .expect("no valid group one found"),
-        )
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        self.optimal_quantum_entanglement(self.params.part1_groups)
+    }
+
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        self.optimal_quantum_entanglement(self.params.part2_groups)
     }
 }
 
-struct PackageDistributor<'a> {
+/// Splits packages into groups of equal weight, minimizing the size and then the quantum
+/// entanglement of the first group
+pub struct PackageDistributor<'a> {
     package_weights: &'a [u64],
     num_groups: u64,
     group_weight: u64,
 }
 
 impl<'a> PackageDistributor<'a> {
-    fn new(package_weights: &'a [u64], num_groups: u64) -> Self {
-        Self {
+    pub fn new(package_weights: &'a [u64], num_groups: u64) -> Result<Self, String> {
+        if num_groups < 2 {
+            return Err(format!("expected at least 2 groups, got {}", num_groups));
+        }
+        let total_weight: u64 = package_weights.iter().copied().sum();
+        if !total_weight.is_multiple_of(num_groups) {
+            return Err(format!("total weight not divisible by {}", num_groups));
+        }
+        Ok(Self {
             package_weights,
             num_groups,
-            group_weight: {
-                let total_weight: u64 = package_weights.into_iter().copied().sum();
-                if total_weight % num_groups == 0 {
-                    total_weight / num_groups
-                } else {
-                    panic!("total weight not divisible by {}", num_groups)
-                }
-            },
-        }
+            group_weight: total_weight / num_groups,
+        })
     }
 
-    fn optimal_group_one(&self) -> Option<Vec<u64>> {
+    pub fn optimal_group_one(&self) -> Option<Vec<u64>> {
         for i in 1..self.package_weights.len() {
             if let Some(group_one) = self.optimal_group_one_of_size(i) {
                 return Some(group_one);
@@ -73,13 +93,12 @@ impl<'a> PackageDistributor<'a> {
 
     fn optimal_group_one_of_size(&self, size: usize) -> Option<Vec<u64>> {
         self.all_valid_groups_one_of_size(size)
-            .min_by_key(|group| quantum_entanglement(&group))
+            .min_by_key(|group| quantum_entanglement(group))
     }
 
     fn all_valid_groups_one_of_size(&self, size: usize) -> impl Iterator<Item = Vec<u64>> + '_ {
         self.all_ways_to_take_group_of_size(size, self.package_weights)
             .filter_map(|(group_one, remainder)| {
-                // if self.can_take_valid_group(&remainder) {
                 if self.can_take_n_valid_groups(&remainder, self.num_groups - 1) {
                     Some(group_one)
                 } else {
@@ -106,10 +125,6 @@ impl<'a> PackageDistributor<'a> {
             .next()
     }
 
-    fn can_take_valid_group(&self, package_weights: &[u64]) -> bool {
-        self.take_valid_group(package_weights).is_some()
-    }
-
     /// Returns an iterator where each element is a tuple of a vector containing the selected
     /// elements (which sum to group_weight) and a vector containing the remaining elements
     fn all_ways_to_take_group_of_size(
@@ -124,8 +139,8 @@ impl<'a> PackageDistributor<'a> {
     }
 }
 
-fn quantum_entanglement(group: &[u64]) -> u64 {
-    group.into_iter().copied().product()
+pub fn quantum_entanglement(group: &[u64]) -> u64 {
+    group.iter().copied().product()
 }
 
 fn take_selection(
@@ -136,7 +151,7 @@ fn take_selection(
     let mut remainder = Vec::with_capacity(available_elements.len() - selection_indices.len());
     selection_indices.sort();
     let mut selection_indices = selection_indices.into_iter().peekable();
-    for (i, element) in available_elements.into_iter().copied().enumerate() {
+    for (i, element) in available_elements.iter().copied().enumerate() {
         if selection_indices.next_if_eq(&i).is_some() {
             selected.push(element);
         } else {
@@ -160,10 +175,14 @@ mod tests {
 
     #[test]
     fn test_package_distributor() {
-        let distributor = PackageDistributor::new(&[1, 2, 3, 4, 5, 7, 8, 9, 10, 11], 3);
+        let distributor = PackageDistributor::new(&[1, 2, 3, 4, 5, 7, 8, 9, 10, 11], 3).unwrap();
 
         let mut optimal_group_one = distributor.optimal_group_one().unwrap();
         optimal_group_one.sort();
-        assert_eq!(optimal_group_one, vec![9, 11])
+        assert_eq!(optimal_group_one, vec![9, 11]);
+
+        assert!(PackageDistributor::new(&[1, 2, 3], 0).is_err());
+        assert!(PackageDistributor::new(&[1, 2, 3], 1).is_err());
+        assert!(PackageDistributor::new(&[1, 2, 3], 4).is_err());
     }
 }
@@ -1,41 +1,68 @@
-use super::{Challenge, NotImplemented};
+use super::{Challenge, ChallengeError, ChallengeResult, NotImplemented};
+use crate::params::{day_params, Params};
 
 pub struct Day25 {
     required_coord: Coord,
 }
 
+day_params! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Day25Params {
+        /// The row of the sheet with the code for the weather machine
+        row: u64 = 2947,
+        /// The column of the sheet with the code for the weather machine
+        column: u64 = 3029,
+    }
+}
+
 impl Challenge for Day25 {
     const DAY: u8 = 25;
 
     type Part1Solution = u64;
     type Part2Solution = NotImplemented;
 
-    fn new(_input: &str) -> Self {
+    const PARAMS: &'static [&'static str] = Day25Params::NAMES;
+
+    fn new(input: &str) -> ChallengeResult<Self> {
+        Self::with_params(input, &Params::default())
+    }
+
+    fn with_params(_input: &str, params: &Params) -> ChallengeResult<Self> {
         // I couldn't be arsed to parse the input for this one..
-        Self {
+        let params = Day25Params::from_params(params)?;
+        // the sheet starts at row 1, column 1, anywhere else the code would never be found
+        if params.row == 0 || params.column == 0 {
+            return Err(ChallengeError::new(format!(
+                "no code at row {}, column {}: rows and columns start at 1",
+                params.row, params.column
+            )));
+        }
+        Ok(Self {
             required_coord: Coord {
-                row: 2947,
-                column: 3029,
+                row: params.row,
+                column: params.column,
             },
-        }
+        })
     }
 
-    fn solve_part1(&self) -> Self::Part1Solution {
-        find_code_at_coord(self.required_coord)
+    fn solve_part1(&self) -> ChallengeResult<Self::Part1Solution> {
+        Ok(find_code_at_coord(self.required_coord))
     }
 
-    fn solve_part2(&self) -> Self::Part2Solution {
-        NotImplemented
+    fn solve_part2(&self) -> ChallengeResult<Self::Part2Solution> {
+        Ok(NotImplemented)
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Coord {
-    row: u64,
-    column: u64,
+pub struct Coord {
+    pub row: u64,
+    pub column: u64,
 }
 
-struct CodeGenerator {
+/// Yields the codes of the infinite sheet in the order they are filled in
+#[derive(Debug)]
+pub struct CodeGenerator {
     coord: Coord,
     code: u64,
 }
@@ -43,7 +70,7 @@ struct CodeGenerator {
 impl CodeGenerator {
     const FIRST_CODE: u64 = 20151125;
 
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             coord: Coord { row: 1, column: 1 },
             code: Self::FIRST_CODE,
@@ -70,7 +97,13 @@ impl CodeGenerator {
     }
 }
 
-fn find_code_at_coord(coord: Coord) -> u64 {
+impl Default for CodeGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn find_code_at_coord(coord: Coord) -> u64 {
     CodeGenerator::new()
         .find_map(|(current_coord, code)| {
             if current_coord == coord {
@@ -97,6 +130,22 @@ impl Iterator for CodeGenerator {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_coord_params() {
+        let mut params = Params::default();
+        params.set("row", "2");
+        params.set("column", "3");
+        assert_eq!(
+            Day25::with_params("", &params).unwrap().solve_part1(),
+            Ok(16929656)
+        );
+        params.set("row", "0");
+        assert!(Day25::with_params("", &params).is_err());
+        params.set("row", "1");
+        params.set("column", "0");
+        assert!(Day25::with_params("", &params).is_err());
+    }
+
     #[test]
     fn test_code_generator() {
         //    |    1         2         3         4         5         6
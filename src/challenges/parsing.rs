@@ -0,0 +1,272 @@
+//! Building blocks for the hand-written parsers of the day modules
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use super::{ChallengeError, LineError};
+
+/// How much of the remaining input a [`ParseError`] shows
+const SNIPPET_LEN: usize = 20;
+
+/// Why parsing failed, and where: the byte offset into the parsed text plus the text found there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    offset: usize,
+    snippet: String,
+    message: String,
+}
+
+impl ParseError {
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.snippet.is_empty() {
+            write!(
+                f,
+                "{} at offset {}, found end of input",
+                self.message, self.offset
+            )
+        } else {
+            write!(
+                f,
+                "{} at offset {}, found {:?}",
+                self.message, self.offset, self.snippet
+            )
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl LineError for ParseError {
+    /// Reports the offset as a column rather than in the message
+    fn at_line(self, line: usize) -> ChallengeError {
+        let message = if self.snippet.is_empty() {
+            format!("{}, found end of input", self.message)
+        } else {
+            format!("{}, found {:?}", self.message, self.snippet)
+        };
+        ChallengeError::at(line, self.offset + 1, message)
+    }
+}
+
+/// A cursor over the text to parse. Every method either consumes what it was asked for or
+/// returns an error and leaves the cursor where it was
+#[derive(Debug, Clone)]
+pub struct Parser<'a> {
+    input: &'a str,
+    cursor: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input, cursor: 0 }
+    }
+
+    pub fn offset(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn remaining_input(&self) -> &'a str {
+        &self.input[self.cursor..]
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.cursor == self.input.len()
+    }
+
+    /// An error at the current position
+    pub fn error(&self, message: impl Display) -> ParseError {
+        self.error_at(self.cursor, message)
+    }
+
+    /// An error at an earlier position, e.g. the start of a token that turned out to be invalid
+    pub fn error_at(&self, offset: usize, message: impl Display) -> ParseError {
+        let remaining = &self.input[offset..];
+        let snippet = match remaining.char_indices().nth(SNIPPET_LEN) {
+            Some((end, _)) => format!("{}...", &remaining[..end]),
+            None => remaining.to_owned(),
+        };
+        ParseError {
+            offset,
+            snippet,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn peek_char(&self) -> Option<char> {
+        self.remaining_input().chars().next()
+    }
+
+    pub fn next_char(&mut self) -> Option<char> {
+        let c = self.peek_char()?;
+        self.cursor += c.len_utf8();
+        Some(c)
+    }
+
+    /// Consumes the next character if it satisfies `predicate`, `expected` describes it for the
+    /// error message
+    pub fn expect_char(
+        &mut self,
+        expected: &str,
+        predicate: impl FnOnce(char) -> bool,
+    ) -> Result<char, ParseError> {
+        match self.peek_char() {
+            Some(c) if predicate(c) => {
+                self.cursor += c.len_utf8();
+                Ok(c)
+            }
+            _ => Err(self.error(format!("expected {}", expected))),
+        }
+    }
+
+    /// Consumes characters as long as they satisfy `predicate`, possibly none
+    pub fn take_while(&mut self, mut predicate: impl FnMut(char) -> bool) -> &'a str {
+        let remaining = self.remaining_input();
+        let len = remaining.find(|c| !predicate(c)).unwrap_or(remaining.len());
+        self.cursor += len;
+        &remaining[..len]
+    }
+
+    /// Consumes `literal` if the remaining input starts with it, returns whether it did
+    pub fn try_literal(&mut self, literal: &str) -> bool {
+        if self.remaining_input().starts_with(literal) {
+            self.cursor += literal.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn expect_literal(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.try_literal(literal) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected {:?}", literal)))
+        }
+    }
+
+    /// Parses a run of ASCII digits as a `T`
+    pub fn parse_number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let start = self.cursor;
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        if digits.is_empty() {
+            return Err(self.error("expected a number"));
+        }
+        digits.parse().map_err(|_| {
+            self.cursor = start;
+            self.error("number out of range")
+        })
+    }
+
+    pub fn expect_end(&self) -> Result<(), ParseError> {
+        if self.is_at_end() {
+            Ok(())
+        } else {
+            Err(self.error("expected end of input"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::day06::Day06;
+    use crate::challenges::{parse_lines, Challenge, Location};
+
+    #[test]
+    fn test_take_while() {
+        let mut parser = Parser::new("abc123");
+        assert_eq!(parser.take_while(char::is_alphabetic), "abc");
+        assert_eq!(parser.take_while(char::is_alphabetic), "");
+        assert_eq!(parser.take_while(char::is_numeric), "123");
+        assert!(parser.is_at_end());
+    }
+
+    #[test]
+    fn test_expect_literal() {
+        let mut parser = Parser::new("turn on 1,2");
+        assert_eq!(parser.expect_literal("turn "), Ok(()));
+        let error = parser.expect_literal("off").unwrap_err();
+        assert_eq!(error.offset(), 5);
+        assert_eq!(
+            error.to_string(),
+            "expected \"off\" at offset 5, found \"on 1,2\""
+        );
+        assert_eq!(parser.offset(), 5);
+        assert!(parser.try_literal("on"));
+    }
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(Parser::new("456").parse_number::<u32>(), Ok(456));
+
+        let mut parser = Parser::new("12 and more");
+        assert_eq!(parser.parse_number::<u32>(), Ok(12));
+        assert_eq!(parser.remaining_input(), " and more");
+
+        assert!(Parser::new("").parse_number::<u32>().is_err());
+        assert!(Parser::new(" abc ").parse_number::<u32>().is_err());
+
+        let mut parser = Parser::new("x 300");
+        parser.expect_literal("x ").unwrap();
+        let error = parser.parse_number::<u8>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "number out of range at offset 2, found \"300\""
+        );
+    }
+
+    #[test]
+    fn test_expect_end() {
+        let mut parser = Parser::new("done.");
+        parser.expect_literal("done").unwrap();
+        assert_eq!(
+            parser.expect_end().unwrap_err().to_string(),
+            "expected end of input at offset 4, found \".\""
+        );
+        parser.expect_literal(".").unwrap();
+        assert_eq!(parser.expect_end(), Ok(()));
+        assert_eq!(
+            parser.expect_literal("!").unwrap_err().to_string(),
+            "expected \"!\" at offset 5, found end of input"
+        );
+    }
+
+    #[test]
+    fn test_snippet_is_truncated() {
+        let parser = Parser::new("the quick brown fox jumps over the lazy dog");
+        assert_eq!(
+            parser.error("nope").to_string(),
+            "nope at offset 0, found \"the quick brown fox ...\""
+        );
+    }
+
+    #[test]
+    fn test_parse_lines_reports_column() {
+        let error = parse_lines::<u32>("1\n2\nx").unwrap_err();
+        assert_eq!(
+            error.location,
+            Some(Location {
+                line: 3,
+                column: None
+            })
+        );
+
+        let error = Day06::new("turn on 0,0 through 9,9\ntoggle 1,2 thru 3,4")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 11: expected \" through \", found \" thru 3,4\""
+        );
+    }
+}
@@ -1,8 +1,382 @@
-use clap::Parser;
+use std::collections::BTreeSet;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use clap::{Parser, Subcommand};
+
+use aoc2015::challenges::day23::Breakpoint;
+use aoc2015::output::OutputFormat;
+use aoc2015::{InputSource, Params, Part};
 
 /// Michael's solutions for Advent of Code 2015
 #[derive(Parser, Debug)]
+#[command(subcommand_negates_reqs = true)]
 pub struct Args {
-    /// Which day to solve (1-25)
-    pub day: u8,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Which days to solve: a single day (7), a range (3..=7 or 3..8), a list (1,4,22) or `all`
+    #[arg(required = true)]
+    pub days: Option<DaySelection>,
+
+    /// Read the puzzle input from this file instead of the input directory, `-` for stdin.
+    /// Only valid when solving a single day
+    #[arg(long, value_name = "PATH", global = true)]
+    pub input: Option<PathBuf>,
+
+    /// Directory holding the puzzle inputs as dayNN.txt
+    #[arg(
+        long,
+        value_name = "DIR",
+        env = "AOC2015_INPUT_DIR",
+        default_value = "input",
+        global = true
+    )]
+    pub input_dir: PathBuf,
+
+    /// Run each phase N times and report min, median and max run times
+    #[arg(long, value_name = "N", default_value = "1")]
+    pub bench: NonZeroUsize,
+
+    /// Print the total run time of all solved days, only used by the text format
+    #[arg(long)]
+    pub total: bool,
+
+    /// Solve only part 1 or part 2
+    #[arg(long, value_name = "1|2", value_parser = parse_part, global = true)]
+    pub part: Option<Part>,
+
+    /// Also explain how the answers were reached, for days that support it
+    #[arg(long)]
+    pub explain: bool,
+
+    /// Override a puzzle constant of a selected day, e.g. `--param liters=25` for day 17. Can be
+    /// repeated
+    #[arg(long, value_name = "NAME=VALUE", value_parser = parse_param, global = true)]
+    pub param: Vec<(String, String)>,
+
+    /// JSON file with puzzle constant overrides, e.g. `{"liters": 25}`. `--param` takes precedence
+    #[arg(long, value_name = "PATH", global = true)]
+    pub params: Option<PathBuf>,
+
+    /// Instead of solving, write the day 7 circuit as a Graphviz graph to PATH (`-` for stdout),
+    /// labelling the wires with their signals
+    #[arg(long, value_name = "PATH")]
+    pub dot: Option<PathBuf>,
+
+    /// Solve up to N days in parallel, solutions are still printed in day order
+    #[arg(long, value_name = "N", default_value = "1", global = true)]
+    pub jobs: NonZeroUsize,
+
+    /// How to print the solutions
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Compare the solutions against known-correct answers
+    Verify {
+        /// Which days to verify, same syntax as for solving
+        #[arg(default_value = "all")]
+        days: DaySelection,
+
+        /// JSON file with the answers, defaults to answers.json in the input directory
+        #[arg(long, value_name = "PATH")]
+        answers: Option<PathBuf>,
+    },
+    /// Solve a day again every time its input file changes and show how the answers changed
+    Watch {
+        /// The day to watch
+        #[arg(value_parser = parse_day)]
+        day: u8,
+
+        /// How often to check the input file for changes, in milliseconds
+        #[arg(long, value_name = "MS", default_value_t = 500)]
+        poll_interval: u64,
+    },
+    /// Step through the day 23 program, with breakpoints and a trace. Starts with a=1 for
+    /// `--part 2`
+    Debug {
+        /// Stop when the program counter reaches N (`pc=N`) or a register condition becomes true
+        /// (e.g. `a==1`, `b>100`). Can be repeated, more can be added while debugging
+        #[arg(long = "break", value_name = "BREAKPOINT", value_parser = parse_breakpoint)]
+        breakpoints: Vec<Breakpoint>,
+
+        /// Print every executed instruction
+        #[arg(long)]
+        trace: bool,
+
+        /// Stop after executing this many instructions, for programs that never halt
+        #[arg(long, value_name = "N", default_value_t = 10_000_000)]
+        max_cycles: u64,
+    },
+    /// Fight the day 22 boss yourself, picking a spell each turn, and compare the mana spent
+    /// with the cheapest win. Plays on hard difficulty for `--part 2`
+    Play,
+}
+
+impl Args {
+    pub fn input_source(&self) -> InputSource {
+        match &self.input {
+            Some(path) if path == Path::new("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(path.clone()),
+            None => InputSource::Directory(self.input_dir.clone()),
+        }
+    }
+
+    /// The overrides from `--params`, updated with those given by `--param`
+    pub fn params(&self) -> Result<Params, String> {
+        let mut params = match &self.params {
+            Some(path) => Params::load(path)?,
+            None => Params::default(),
+        };
+        for (name, value) in &self.param {
+            params.set(name.as_str(), value.as_str());
+        }
+        Ok(params)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaySelection {
+    All,
+    Days(Vec<u8>),
+}
+
+impl DaySelection {
+    pub fn resolve(&self, registered_days: impl IntoIterator<Item = u8>) -> Vec<u8> {
+        match self {
+            DaySelection::All => registered_days.into_iter().collect(),
+            DaySelection::Days(days) => days.clone(),
+        }
+    }
+}
+
+type ParseError = String;
+
+impl FromStr for DaySelection {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(DaySelection::All);
+        }
+        // solve each day once and in order, however they were listed
+        let mut days = BTreeSet::new();
+        for item in s.split(',') {
+            days.extend(parse_days(item.trim())?);
+        }
+        Ok(DaySelection::Days(days.into_iter().collect()))
+    }
+}
+
+fn parse_days(s: &str) -> Result<Vec<u8>, ParseError> {
+    let days: Vec<_> = if let Some((first, last)) = s.split_once("..=") {
+        (parse_day(first)?..=parse_day(last)?).collect()
+    } else if let Some((first, end)) = s.split_once("..") {
+        let end = end
+            .parse::<u8>()
+            .ok()
+            .filter(|end| (2..=26).contains(end))
+            .ok_or_else(|| format!("invalid range end: {:?}", end))?;
+        (parse_day(first)?..end).collect()
+    } else {
+        vec![parse_day(s)?]
+    };
+    if days.is_empty() {
+        return Err(format!("empty range: {:?}", s));
+    }
+    Ok(days)
+}
+
+/// A day of the advent calendar, 1 to 25
+fn parse_day(s: &str) -> Result<u8, ParseError> {
+    s.parse::<u8>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| format!("invalid day: {:?}, expected 1 to 25", s))
+}
+
+fn parse_part(s: &str) -> Result<Part, ParseError> {
+    match s {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("invalid part: {:?}", s)),
+    }
+}
+
+fn parse_breakpoint(s: &str) -> Result<Breakpoint, ParseError> {
+    s.parse()
+        .map_err(|error| format!("invalid breakpoint: {}", error))
+}
+
+fn parse_param(s: &str) -> Result<(String, String), ParseError> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_owned(), value.trim().to_owned()))
+        }
+        _ => Err(format!("expected NAME=VALUE: {:?}", s)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_selection_parsing() {
+        assert_eq!("all".parse(), Ok(DaySelection::All));
+        assert_eq!("7".parse(), Ok(DaySelection::Days(vec![7])));
+        assert_eq!("3..=5".parse(), Ok(DaySelection::Days(vec![3, 4, 5])));
+        assert_eq!("3..5".parse(), Ok(DaySelection::Days(vec![3, 4])));
+        assert_eq!("1,4,22".parse(), Ok(DaySelection::Days(vec![1, 4, 22])));
+        assert_eq!(
+            "1, 10..=12".parse(),
+            Ok(DaySelection::Days(vec![1, 10, 11, 12]))
+        );
+        assert_eq!("3,1,3".parse(), Ok(DaySelection::Days(vec![1, 3])));
+        assert_eq!("1,1".parse(), Ok(DaySelection::Days(vec![1])));
+        assert_eq!(
+            "2..=4,1..3".parse(),
+            Ok(DaySelection::Days(vec![1, 2, 3, 4]))
+        );
+        assert!("".parse::<DaySelection>().is_err());
+        assert!("one".parse::<DaySelection>().is_err());
+        assert!("1..=x".parse::<DaySelection>().is_err());
+        assert_eq!("24..26".parse(), Ok(DaySelection::Days(vec![24, 25])));
+        assert_eq!(
+            "7..3".parse::<DaySelection>(),
+            Err("empty range: \"7..3\"".to_owned())
+        );
+        assert!("5..5".parse::<DaySelection>().is_err());
+        assert!("7..=3".parse::<DaySelection>().is_err());
+        assert_eq!(
+            "0".parse::<DaySelection>(),
+            Err("invalid day: \"0\", expected 1 to 25".to_owned())
+        );
+        assert!("99".parse::<DaySelection>().is_err());
+        assert!("20..=26".parse::<DaySelection>().is_err());
+        assert!("20..27".parse::<DaySelection>().is_err());
+    }
+
+    #[test]
+    fn test_day_selection_resolve() {
+        assert_eq!(DaySelection::All.resolve([1, 2, 3]), vec![1, 2, 3]);
+        assert_eq!(DaySelection::Days(vec![2]).resolve([1, 2, 3]), vec![2]);
+    }
+
+    #[test]
+    fn test_input_source() {
+        let input_source = |args: &[&str]| {
+            Args::try_parse_from([&["aoc2015", "7"], args].concat())
+                .unwrap()
+                .input_source()
+        };
+        assert_eq!(
+            input_source(&["--input-dir", "inputs"]),
+            InputSource::Directory(PathBuf::from("inputs"))
+        );
+        assert_eq!(
+            input_source(&["--input", "day7.txt"]),
+            InputSource::File(PathBuf::from("day7.txt"))
+        );
+        assert_eq!(input_source(&["--input", "-"]), InputSource::Stdin);
+    }
+
+    #[test]
+    fn test_verify_command() {
+        let args = Args::try_parse_from(["aoc2015", "verify"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Some(Command::Verify {
+                days: DaySelection::All,
+                answers: None
+            })
+        ));
+
+        let args =
+            Args::try_parse_from(["aoc2015", "verify", "3..5", "--answers", "a.json"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Some(Command::Verify { days: DaySelection::Days(days), answers: Some(_) }) if days == [3, 4]
+        ));
+
+        assert!(Args::try_parse_from(["aoc2015"]).is_err());
+        assert!(Args::try_parse_from(["aoc2015", "7"])
+            .unwrap()
+            .command
+            .is_none());
+    }
+
+    #[test]
+    fn test_part_parsing() {
+        assert_eq!(parse_part("1"), Ok(Part::One));
+        assert_eq!(parse_part("2"), Ok(Part::Two));
+        assert!(parse_part("3").is_err());
+    }
+
+    #[test]
+    fn test_watch_command() {
+        let args =
+            Args::try_parse_from(["aoc2015", "watch", "7", "--input-dir", "inputs"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Some(Command::Watch {
+                day: 7,
+                poll_interval: 500
+            })
+        ));
+        assert_eq!(args.input_dir, PathBuf::from("inputs"));
+        assert!(Args::try_parse_from(["aoc2015", "watch", "x"]).is_err());
+    }
+
+    #[test]
+    fn test_debug_command() {
+        let args = Args::try_parse_from([
+            "aoc2015", "debug", "--break", "pc=3", "--break", "a>=2", "--part", "2",
+        ])
+        .unwrap();
+        assert!(matches!(
+            args.command,
+            Some(Command::Debug {
+                breakpoints,
+                trace: false,
+                max_cycles: 10_000_000
+            }) if breakpoints == ["pc=3".parse().unwrap(), "a>=2".parse().unwrap()]
+        ));
+        assert_eq!(args.part, Some(Part::Two));
+        assert!(Args::try_parse_from(["aoc2015", "debug", "--break", "pc"]).is_err());
+    }
+
+    #[test]
+    fn test_play_command() {
+        let args = Args::try_parse_from(["aoc2015", "play", "--part", "2", "--param", "mana=1000"])
+            .unwrap();
+        assert!(matches!(args.command, Some(Command::Play)));
+        assert_eq!(args.part, Some(Part::Two));
+        assert_eq!(args.param, [("mana".to_owned(), "1000".to_owned())]);
+    }
+
+    #[test]
+    fn test_params() {
+        assert_eq!(
+            parse_param("liters=25"),
+            Ok(("liters".to_owned(), "25".to_owned()))
+        );
+        assert!(parse_param("liters").is_err());
+        assert!(parse_param("=25").is_err());
+
+        let args = Args::try_parse_from([
+            "aoc2015",
+            "17",
+            "--param",
+            "liters=25",
+            "--param",
+            "liters=30",
+        ])
+        .unwrap();
+        assert_eq!(args.params().unwrap().get("liters", 150), Ok(30));
+    }
 }
@@ -0,0 +1,138 @@
+use std::io::BufRead;
+
+use crate::challenges::day23::{self, Debugger, Stop};
+
+const HELP: &str = "\
+commands:
+  s, step [N]        execute N instructions (default 1)
+  c, continue        run until a breakpoint is hit or the program halts
+  b, break BP        add a breakpoint: pc=N, or a register condition like a==1 or b>100
+  d, delete N        remove breakpoint N
+  l, list            list the breakpoints
+  p, program         print the disassembled program
+  t, trace on|off    print every executed instruction
+  r, registers       print the program counter and registers
+  h, hits            print how often each instruction was executed
+  q, quit            stop debugging";
+
+/// Debugs a day 23 program, reading commands line by line from `commands` until the program halts
+/// or the user quits. Prints the hit counts at the end and returns whether the program halted
+pub fn debug(debugger: &mut Debugger, commands: impl BufRead) -> bool {
+    println!("{}", HELP);
+    print_state(debugger);
+    let mut halted = false;
+    for line in commands.lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            continue;
+        };
+        let argument = words.next();
+        let stop = match (command, argument) {
+            ("s" | "step", count) => match count.map_or(Ok(1), str::parse) {
+                Ok(count) => Some(debugger.step(count)),
+                Err(_) => {
+                    eprintln!("error: invalid step count");
+                    None
+                }
+            },
+            ("c" | "continue", None) => Some(debugger.continue_execution()),
+            ("b" | "break", Some(breakpoint)) => {
+                match breakpoint.parse() {
+                    Ok(breakpoint) => {
+                        let index = debugger.add_breakpoint(breakpoint);
+                        println!("breakpoint {}: {}", index, breakpoint);
+                    }
+                    Err(error) => eprintln!("error: {}", error),
+                }
+                None
+            }
+            ("d" | "delete", Some(index)) => {
+                match index
+                    .parse()
+                    .ok()
+                    .and_then(|index| debugger.remove_breakpoint(index))
+                {
+                    Some(breakpoint) => println!("removed {}", breakpoint),
+                    None => eprintln!("error: no breakpoint {}", index),
+                }
+                None
+            }
+            ("l" | "list", None) => {
+                for (index, breakpoint) in debugger.breakpoints().iter().enumerate() {
+                    println!("breakpoint {}: {}", index, breakpoint);
+                }
+                None
+            }
+            ("p" | "program", None) => {
+                print!("{}", day23::disassemble(debugger.computer().instructions()));
+                None
+            }
+            ("t" | "trace", Some(setting @ ("on" | "off"))) => {
+                debugger.set_tracing(setting == "on");
+                None
+            }
+            ("r" | "registers", None) => {
+                print_state(debugger);
+                None
+            }
+            ("h" | "hits", None) => {
+                print_hit_counts(debugger);
+                None
+            }
+            ("q" | "quit", None) => break,
+            _ => {
+                eprintln!("error: unknown command {:?}", line.trim());
+                None
+            }
+        };
+        if let Some(stop) = stop {
+            for entry in debugger.take_trace() {
+                println!("{}", entry);
+            }
+            match stop {
+                Stop::Stepped => {}
+                Stop::Breakpoint(index) => {
+                    println!(
+                        "hit breakpoint {}: {}",
+                        index,
+                        debugger.breakpoints()[index]
+                    )
+                }
+                Stop::Halted => println!("halted"),
+                Stop::Fault(error) => println!("failed: {}", error),
+                Stop::CycleLimit => println!("stopped after {} cycles", debugger.cycles()),
+            }
+            print_state(debugger);
+            if stop == Stop::Halted {
+                halted = true;
+                break;
+            }
+        }
+    }
+    println!();
+    print_hit_counts(debugger);
+    halted
+}
+
+fn print_state(debugger: &Debugger) {
+    let computer = debugger.computer();
+    match computer.current_instruction() {
+        Some(instruction) => println!(
+            "pc={} {} next: {}",
+            computer.pc(),
+            computer.registers(),
+            instruction
+        ),
+        None => println!("pc={} {}", computer.pc(), computer.registers()),
+    }
+}
+
+fn print_hit_counts(debugger: &Debugger) {
+    println!("executed {} instructions:", debugger.cycles());
+    for (pc, hits) in debugger.hit_counts() {
+        println!("{:>4}: {}", pc, hits);
+    }
+}
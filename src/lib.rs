@@ -0,0 +1,17 @@
+//! Solutions for Advent of Code 2015, usable as a library: the [`Challenges`] registry runs any
+//! day, and the day modules expose their simulators (e.g. [`challenges::day07::Emulator`],
+//! [`challenges::day22::Game`], [`challenges::day23::Computer`]).
+
+pub mod answers;
+pub mod challenges;
+pub mod debug;
+pub mod output;
+pub mod params;
+pub mod play;
+pub mod watch;
+
+pub use challenges::{
+    Challenge, ChallengeError, ChallengeResult, Challenges, DaySolutions, Explain, InputSource,
+    NotImplemented, Part, PartSolution, SolveOptions, Timing,
+};
+pub use params::Params;
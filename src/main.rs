@@ -1,13 +1,214 @@
-#![allow(dead_code)]
-
-mod challenges;
 mod cli;
 
-use challenges::Challenges;
-use clap::Parser;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc2015::answers::{self, Answers};
+use aoc2015::challenges::day07::{self, Day07, Evaluator};
+use aoc2015::challenges::day22::{Day22, Difficulty};
+use aoc2015::challenges::day23::{Computer, Day23, Debugger, Register};
+use aoc2015::{debug, output, play, watch, Challenge, Challenges, InputSource, Part, SolveOptions};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use cli::Command;
 
-fn main() {
+fn main() -> ExitCode {
     let args = cli::Args::parse();
     let challenges = Challenges::new();
-    challenges.print_solutions(args.day);
+    let days = match &args.command {
+        Some(Command::Verify { days, .. }) => days.resolve(challenges.days()),
+        Some(Command::Watch { day, .. }) => vec![*day],
+        Some(Command::Debug { .. }) => vec![23],
+        Some(Command::Play) => vec![22],
+        None => args
+            .days
+            .as_ref()
+            .expect("days are required without a subcommand")
+            .resolve(challenges.days()),
+    };
+    if args.input.is_some() && days.len() != 1 {
+        cli::Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--input can only be used when solving a single day",
+            )
+            .exit();
+    }
+    let params = match args.params() {
+        Ok(params) => params,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(name) = challenges.unknown_param(&days, &params) {
+        cli::Args::command()
+            .error(
+                ErrorKind::InvalidValue,
+                format!("no selected day has a param named {:?}", name),
+            )
+            .exit();
+    }
+
+    if let Some(dot_path) = &args.dot {
+        if args.command.is_some() || days != [7] {
+            cli::Args::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    "--dot can only be used when solving day 7",
+                )
+                .exit();
+        }
+        return match write_circuit_dot(&args.input_source(), dot_path) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("error: {}", error);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let success = match &args.command {
+        Some(Command::Verify { answers, .. }) => {
+            let answers_path = answers
+                .clone()
+                .unwrap_or_else(|| args.input_dir.join("answers.json"));
+            match Answers::load(&answers_path) {
+                Ok(answers) => answers::verify_days(
+                    &challenges,
+                    &days,
+                    &args.input_source(),
+                    &SolveOptions {
+                        part: args.part,
+                        params,
+                        ..SolveOptions::default()
+                    },
+                    args.jobs.get(),
+                    &answers,
+                ),
+                Err(error) => {
+                    eprintln!("error: {}", error);
+                    false
+                }
+            }
+        }
+        Some(Command::Watch { day, poll_interval }) => {
+            let Some(path) = args.input_source().path(*day) else {
+                cli::Args::command()
+                    .error(
+                        ErrorKind::ArgumentConflict,
+                        "watch needs an input file, it can't read from stdin",
+                    )
+                    .exit();
+            };
+            watch::watch(
+                &challenges,
+                *day,
+                &path,
+                &SolveOptions {
+                    part: args.part,
+                    params,
+                    ..SolveOptions::default()
+                },
+                Duration::from_millis(*poll_interval),
+            )
+        }
+        Some(Command::Debug {
+            breakpoints,
+            trace,
+            max_cycles,
+        }) => match args
+            .input_source()
+            .read(23)
+            .and_then(|input| Day23::new(&input))
+        {
+            Ok(day) => {
+                let mut computer = Computer::with_instructions(day.instructions().to_vec());
+                if args.part == Some(Part::Two) {
+                    computer
+                        .registers_mut()
+                        .set(Register::A, 1u32)
+                        .expect("the computer has an a register");
+                }
+                let mut debugger = Debugger::new(computer, *max_cycles);
+                for &breakpoint in breakpoints {
+                    debugger.add_breakpoint(breakpoint);
+                }
+                debugger.set_tracing(*trace);
+                debug::debug(&mut debugger, io::stdin().lock())
+            }
+            Err(error) => {
+                eprintln!("error: {}", error);
+                false
+            }
+        },
+        Some(Command::Play) => match args
+            .input_source()
+            .read(22)
+            .and_then(|input| Day22::with_params(&input, &params))
+        {
+            Ok(day) => {
+                let difficulty = if args.part == Some(Part::Two) {
+                    Difficulty::Hard
+                } else {
+                    Difficulty::Normal
+                };
+                play::play(day.initial_state(difficulty), io::stdin().lock()).is_some()
+            }
+            Err(error) => {
+                eprintln!("error: {}", error);
+                false
+            }
+        },
+        None => {
+            let options = SolveOptions {
+                repetitions: args.bench.get(),
+                part: args.part,
+                explain: args.explain,
+                params,
+            };
+            output::print_solutions_for_days(
+                &challenges,
+                &days,
+                &args.input_source(),
+                &options,
+                args.jobs.get(),
+                args.format,
+                args.total,
+            )
+        }
+    };
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Writes the day 7 circuit as a Graphviz graph, with signals if the whole circuit can be evaluated
+fn write_circuit_dot(input: &InputSource, path: &Path) -> Result<(), String> {
+    let day = Day07::new(&input.read(7).map_err(|error| error.to_string())?)
+        .map_err(|error| error.to_string())?;
+    let instructions = day.instructions();
+    let wire_values = Evaluator::new(instructions).and_then(|mut evaluator| {
+        evaluator.wire_values(
+            instructions
+                .iter()
+                .map(|instruction| instruction.output.as_str()),
+        )
+    });
+    if let Err(error) = &wire_values {
+        eprintln!("warning: leaving out the signals: {}", error);
+    }
+    let dot = day07::to_dot(instructions, wire_values.as_ref().ok());
+    if path == Path::new("-") {
+        print!("{}", dot);
+        Ok(())
+    } else {
+        fs::write(path, dot)
+            .map_err(|error| format!("could not write {}: {}", path.display(), error))
+    }
 }
@@ -0,0 +1,347 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+use serde_json::{json, Value};
+
+use crate::challenges::{
+    ChallengeResult, Challenges, DaySolutions, InputSource, PartSolution, SolveOptions, Timing,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable solutions followed by a summary table
+    Text,
+    /// A JSON array with one object per day
+    Json,
+    /// One row per day, timings in nanoseconds
+    Csv,
+}
+
+type DayOutcome = (u8, ChallengeResult<DaySolutions>);
+
+/// Solves the given days and prints the solutions in `format` as they come in. Returns whether
+/// all days were solved without errors
+pub fn print_solutions_for_days(
+    challenges: &Challenges,
+    days: &[u8],
+    input: &InputSource,
+    options: &SolveOptions,
+    jobs: usize,
+    format: OutputFormat,
+    print_total: bool,
+) -> bool {
+    if format == OutputFormat::Csv {
+        println!("{}", CSV_HEADER.join(","));
+    }
+    let mut outcomes = Vec::with_capacity(days.len());
+    challenges.solve_days(days, input, options, jobs, |day, outcome| {
+        let outcome = (day, outcome);
+        match format {
+            OutputFormat::Text => print_text(&outcome),
+            OutputFormat::Json => {}
+            OutputFormat::Csv => println!("{}", csv_row(&outcome).join(",")),
+        }
+        outcomes.push(outcome);
+    });
+
+    match format {
+        OutputFormat::Text => {
+            if outcomes.len() > 1 {
+                print_summary(&outcomes);
+            }
+            if print_total {
+                let total: Duration = outcomes
+                    .iter()
+                    .filter_map(|(_, outcome)| outcome.as_ref().ok())
+                    .map(DaySolutions::total_time)
+                    .sum();
+                println!();
+                println!("Total time: {:.2?}", total);
+            }
+        }
+        OutputFormat::Json => {
+            let days: Vec<_> = outcomes.iter().map(to_json).collect();
+            println!("{:#}", Value::Array(days));
+        }
+        OutputFormat::Csv => {}
+    }
+
+    outcomes.iter().all(|(_, outcome)| match outcome {
+        Ok(solutions) => solutions.first_error().is_none(),
+        Err(_) => false,
+    })
+}
+
+fn print_text((day, outcome): &DayOutcome) {
+    match outcome {
+        Ok(solutions) => {
+            println!("Solutions for day {}:", day);
+            println!("  parse: {}", solutions.parse_timing);
+            for (part, solution) in solutions.parts() {
+                match &solution.answer {
+                    Ok(answer) => println!("  {}: {} ({})", part, answer, solution.timing),
+                    Err(error) => {
+                        println!("  {}: <failed> ({})", part, solution.timing);
+                        eprintln!("error: {}", error);
+                    }
+                }
+                match &solution.explanation {
+                    Some(Ok(explanation)) => {
+                        for line in explanation.lines() {
+                            println!("    {}", line);
+                        }
+                    }
+                    Some(Err(error)) => eprintln!("error: {}", error),
+                    None => {}
+                }
+            }
+        }
+        Err(error) => {
+            println!("Failed to solve day {}", day);
+            eprintln!("error: {}", error);
+        }
+    }
+}
+
+fn print_summary(outcomes: &[DayOutcome]) {
+    let format_solution = |solution: &Option<PartSolution>| match solution {
+        Some(PartSolution {
+            answer: Ok(answer), ..
+        }) => answer.clone(),
+        Some(PartSolution { answer: Err(_), .. }) => "<failed>".to_owned(),
+        None => "<skipped>".to_owned(),
+    };
+    let rows: Vec<[String; 5]> = outcomes
+        .iter()
+        .map(|(day, outcome)| match outcome {
+            Ok(solutions) => [
+                day.to_string(),
+                match solutions.first_error() {
+                    None => "ok".to_owned(),
+                    Some(error) => format!("failed: {}", error),
+                },
+                format_solution(&solutions.part1),
+                format_solution(&solutions.part2),
+                format!("{:.2?}", solutions.total_time()),
+            ],
+            Err(error) => [
+                day.to_string(),
+                format!("failed: {}", error),
+                String::new(),
+                String::new(),
+                String::new(),
+            ],
+        })
+        .collect();
+    let header = ["day", "status", "part 1", "part 2", "time"].map(String::from);
+
+    let mut widths = header.each_ref().map(|cell| cell.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let format_row = |row: &[String; 5]| {
+        row.iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    };
+    println!();
+    println!("Summary:");
+    println!("  {}", format_row(&header));
+    println!("  {}", widths.map(|width| "-".repeat(width)).join("-+-"));
+    for row in &rows {
+        println!("  {}", format_row(row));
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+fn answer_to_json(solution: &Option<PartSolution>) -> Value {
+    match solution {
+        Some(PartSolution {
+            answer: Ok(answer), ..
+        }) => answer.as_str().into(),
+        _ => Value::Null,
+    }
+}
+
+fn explanation_to_json(solution: &Option<PartSolution>) -> Value {
+    match solution
+        .as_ref()
+        .and_then(|solution| solution.explanation.as_ref())
+    {
+        Some(Ok(explanation)) => explanation.as_str().into(),
+        _ => Value::Null,
+    }
+}
+
+fn timing_to_json(timing: &Timing) -> Value {
+    json!({
+        "repetitions": timing.repetitions,
+        "min_ns": nanos(timing.min),
+        "median_ns": nanos(timing.median),
+        "max_ns": nanos(timing.max),
+    })
+}
+
+fn to_json((day, outcome): &DayOutcome) -> Value {
+    match outcome {
+        Ok(solutions) => json!({
+            "day": day,
+            "part1": answer_to_json(&solutions.part1),
+            "part2": answer_to_json(&solutions.part2),
+            "errors": solutions
+                .parts()
+                .filter_map(|(_, solution)| solution.answer.as_ref().err())
+                .map(|error| error.to_string())
+                .collect::<Vec<_>>(),
+            "explanations": {
+                "part1": explanation_to_json(&solutions.part1),
+                "part2": explanation_to_json(&solutions.part2),
+            },
+            "timings": {
+                "parse": timing_to_json(&solutions.parse_timing),
+                "part1": solutions.part1.as_ref().map(|solution| timing_to_json(&solution.timing)),
+                "part2": solutions.part2.as_ref().map(|solution| timing_to_json(&solution.timing)),
+            },
+        }),
+        Err(error) => json!({
+            "day": day,
+            "part1": null,
+            "part2": null,
+            "errors": [error.to_string()],
+            "explanations": null,
+            "timings": null,
+        }),
+    }
+}
+
+const CSV_HEADER: [&str; 13] = [
+    "day",
+    "part1",
+    "part2",
+    "error",
+    "parse_min_ns",
+    "parse_median_ns",
+    "parse_max_ns",
+    "part1_min_ns",
+    "part1_median_ns",
+    "part1_max_ns",
+    "part2_min_ns",
+    "part2_median_ns",
+    "part2_max_ns",
+];
+
+fn csv_row((day, outcome): &DayOutcome) -> Vec<String> {
+    let mut row = vec![day.to_string()];
+    match outcome {
+        Ok(solutions) => {
+            for solution in [&solutions.part1, &solutions.part2] {
+                let answer = solution
+                    .as_ref()
+                    .and_then(|solution| solution.answer.as_deref().ok());
+                row.push(csv_escape(answer.unwrap_or_default()));
+            }
+            let error = solutions.first_error().map(|error| error.to_string());
+            row.push(csv_escape(&error.unwrap_or_default()));
+            for timing in [
+                Some(solutions.parse_timing),
+                solutions.part1.as_ref().map(|solution| solution.timing),
+                solutions.part2.as_ref().map(|solution| solution.timing),
+            ] {
+                match timing {
+                    Some(timing) => row.extend(
+                        [timing.min, timing.median, timing.max].map(|d| nanos(d).to_string()),
+                    ),
+                    None => row.extend([String::new(), String::new(), String::new()]),
+                }
+            }
+        }
+        Err(error) => {
+            row.extend([String::new(), String::new(), csv_escape(&error.to_string())]);
+            row.resize(CSV_HEADER.len(), String::new());
+        }
+    }
+    row
+}
+
+/// Quotes a field if it contains a separator, a quote or a line break
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::ChallengeError;
+
+    fn timing(nanos: u64) -> Timing {
+        let duration = Duration::from_nanos(nanos);
+        Timing {
+            repetitions: 1,
+            min: duration,
+            median: duration,
+            max: duration,
+        }
+    }
+
+    fn example_outcome() -> DayOutcome {
+        (
+            12,
+            Ok(DaySolutions {
+                parse_timing: timing(10),
+                part1: Some(PartSolution {
+                    answer: Ok("191164".to_owned()),
+                    timing: timing(20),
+                    explanation: Some(Ok("it's just a sum".to_owned())),
+                }),
+                part2: Some(PartSolution {
+                    answer: Err(ChallengeError::new("bad, really bad")),
+                    timing: timing(30),
+                    explanation: None,
+                }),
+            }),
+        )
+    }
+
+    #[test]
+    fn test_csv_escape() {
+        assert_eq!(csv_escape("280"), "280");
+        assert_eq!(csv_escape("a, b"), "\"a, b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_csv_row() {
+        assert_eq!(
+            csv_row(&example_outcome()).join(","),
+            "12,191164,,\"bad, really bad\",10,10,10,20,20,20,30,30,30"
+        );
+        assert_eq!(
+            csv_row(&(26, Err(ChallengeError::new("no solution registered")))).join(","),
+            "26,,,no solution registered,,,,,,,,,"
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json = to_json(&example_outcome());
+        assert_eq!(json["day"], 12);
+        assert_eq!(json["part1"], "191164");
+        assert_eq!(json["part2"], Value::Null);
+        assert_eq!(json["errors"], json!(["bad, really bad"]));
+        assert_eq!(json["explanations"]["part1"], "it's just a sum");
+        assert_eq!(json["explanations"]["part2"], Value::Null);
+        assert_eq!(json["timings"]["part2"]["median_ns"], 30);
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use crate::challenges::{ChallengeError, ChallengeResult};
+
+type Error = String;
+
+/// Overrides for puzzle constants by name, e.g. `liters` = 25 for day 17. The values are only
+/// parsed by the days that read them, into their own typed parameter structs; anything not
+/// overridden keeps the day's default
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    /// Loads overrides from a JSON file of the form `{"liters": 25, "steps": 4}`
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let contents = fs::read_to_string(path)
+            .map_err(|error| format!("could not read {}: {}", path.display(), error))?;
+        contents
+            .parse()
+            .map_err(|error| format!("invalid params file {}: {}", path.display(), error))
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.values.insert(name.into(), value.into());
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    /// The value of `name` parsed as `T`, or `default` if it wasn't overridden
+    pub fn get<T: FromStr>(&self, name: &str, default: T) -> ChallengeResult<T> {
+        Ok(self.get_optional(name)?.unwrap_or(default))
+    }
+
+    /// The value of `name` parsed as `T`, or `None` if it wasn't overridden
+    pub fn get_optional<T: FromStr>(&self, name: &str) -> ChallengeResult<Option<T>> {
+        self.values
+            .get(name)
+            .map(|value| {
+                value.parse().map_err(|_| {
+                    ChallengeError::new(format!("invalid value for param {}: {:?}", name, value))
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Declares a day's puzzle constants as a struct with one field per param, named like the param
+/// and initialized to its default unless overridden. A field whose param isn't parsed directly,
+/// such as the path of a file to load, names a loader after `=>` that builds the field from the
+/// param's value
+macro_rules! day_params {
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident {
+            $(
+                $(#[$field_attr:meta])*
+                $field:ident: $ty:ty = $default:expr $(=> $load:expr)?
+            ),* $(,)?
+        }
+    ) => {
+        /// Puzzle constants that can be overridden with [`Params`](crate::params::Params) by the
+        /// names of the fields
+        $(#[$attr])*
+        pub struct $name {
+            $(
+                $(#[$field_attr])*
+                pub $field: $ty,
+            )*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self {
+                    $($field: $default,)*
+                }
+            }
+        }
+
+        impl $name {
+            /// Names of the params, for [`Challenge::PARAMS`](crate::challenges::Challenge::PARAMS)
+            pub const NAMES: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            pub fn from_params(
+                params: &crate::params::Params,
+            ) -> crate::challenges::ChallengeResult<Self> {
+                let default = Self::default();
+                Ok(Self {
+                    $($field: day_params!(@get params, $field, default.$field $(, $load)?),)*
+                })
+            }
+        }
+    };
+    (@get $params:ident, $field:ident, $default:expr) => {
+        $params.get(stringify!($field), $default)?
+    };
+    (@get $params:ident, $field:ident, $default:expr, $load:expr) => {
+        match $params.get_optional::<String>(stringify!($field))? {
+            Some(value) => $load(&value)?,
+            None => $default,
+        }
+    };
+}
+
+pub(crate) use day_params;
+
+impl FromStr for Params {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value: Value = serde_json::from_str(s).map_err(|error| error.to_string())?;
+        let values = value
+            .as_object()
+            .ok_or("expected an object keyed by param")?;
+        let mut params = Self::default();
+        for (name, value) in values {
+            params.set(
+                name.as_str(),
+                match value {
+                    Value::String(value) => value.clone(),
+                    Value::Number(value) => value.to_string(),
+                    _ => return Err(format!("{}: expected a string or number", name)),
+                },
+            );
+        }
+        Ok(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params() {
+        let mut params: Params = r#"{"liters": 25, "steps": "4"}"#.parse().unwrap();
+        assert_eq!(params.get("liters", 150), Ok(25));
+        assert_eq!(params.get("steps", 100), Ok(4));
+        assert_eq!(params.get("seconds", 2503), Ok(2503));
+        assert_eq!(params.get_optional::<u32>("seconds"), Ok(None));
+
+        params.set("liters", "lots");
+        assert!(params.get("liters", 150).is_err());
+        assert_eq!(params.names().collect::<Vec<_>>(), vec!["liters", "steps"]);
+
+        assert!(r#"{"liters": [25]}"#.parse::<Params>().is_err());
+        assert!("[]".parse::<Params>().is_err());
+    }
+}
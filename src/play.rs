@@ -0,0 +1,181 @@
+use std::io::{self, BufRead, Write};
+
+use crate::challenges::day22::{
+    DijkstraOptimizer, Game, SearchError, Spell, SpellBook, TurnEvent, Winner,
+};
+
+/// Fights the day 22 boss with a spell picked by the user each turn, reading the choices line by
+/// line from `commands`. At the end compares the mana spent with the cheapest possible win and
+/// returns the winner and the mana spent, or `None` if the game was abandoned
+pub fn play(initial_state: Game, commands: impl BufRead) -> Option<(Winner, u32)> {
+    let spell_book = initial_state.spell_book();
+    let mut game = initial_state;
+    let mut mana_spent = 0;
+    let mut lines = commands.lines();
+    let winner = loop {
+        println!();
+        println!("-- Player turn --");
+        println!("{}", game);
+        for spell in spell_book.spells() {
+            let definition = spell_book.definition(spell);
+            println!(
+                "  {}) {} ({} mana)",
+                spell.index() + 1,
+                definition.name,
+                definition.mana_cost
+            );
+        }
+        if !spell_book.spells().any(|spell| game.can_cast(spell)) {
+            println!("You can't cast any spell");
+            break Winner::Boss;
+        }
+        let spell = loop {
+            print!("cast> ");
+            io::stdout().flush().ok();
+            let Some(Ok(line)) = lines.next() else {
+                println!();
+                println!("gave up");
+                return None;
+            };
+            match parse_spell(spell_book, line.trim()) {
+                Some(spell) => break spell,
+                None => eprintln!("error: pick a spell by number or name"),
+            }
+        };
+        // the turn can end the game before the spell is cast, e.g. when poison kills the boss
+        let turn = game.player_take_turn_observed(spell, &mut |event| {
+            if let TurnEvent::Cast(spell) = event {
+                mana_spent += spell_book.definition(spell).mana_cost;
+            }
+            println!("{}", event.describe(spell_book));
+        });
+        match turn {
+            Ok(Some(winner)) => break winner,
+            Ok(None) => {}
+            Err(error) => {
+                eprintln!("error: can't cast {}: {}", spell_book.name(spell), error);
+                continue;
+            }
+        }
+
+        println!();
+        println!("-- Boss turn --");
+        println!("{}", game);
+        let turn = game.boss_take_turn_observed(&mut |event| {
+            println!("{}", event.describe(spell_book));
+        });
+        match turn {
+            Ok(Some(winner)) => break winner,
+            Ok(None) => {}
+            Err(error) => {
+                eprintln!("error: the boss can't take a turn: {}", error);
+                println!("game over");
+                return None;
+            }
+        }
+    };
+
+    println!();
+    println!("{}", game);
+    let optimum = DijkstraOptimizer::new(initial_state).find_lowest_mana_cost_to_win();
+    match winner {
+        Winner::Player => println!("You won, spending {} mana", mana_spent),
+        Winner::Boss => println!("The boss won after you spent {} mana", mana_spent),
+    }
+    match optimum {
+        Ok(optimum) if winner == Winner::Player && mana_spent == optimum => {
+            println!("That's the cheapest possible win")
+        }
+        Ok(optimum) => println!("The cheapest possible win costs {} mana", optimum),
+        Err(SearchError::Unbeatable) => println!("The boss can't be beaten"),
+        Err(error) => eprintln!("error: can't find the cheapest win: {}", error),
+    }
+    Some((winner, mana_spent))
+}
+
+/// A spell by its number in the menu or its name, ignoring case
+fn parse_spell(spell_book: &SpellBook, choice: &str) -> Option<Spell> {
+    if let Ok(number) = choice.parse::<usize>() {
+        return spell_book.spells().nth(number.checked_sub(1)?);
+    }
+    spell_book.find(choice)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::day22::{Boss, Difficulty, Player};
+
+    #[test]
+    fn test_parse_spell() {
+        let spell_book = SpellBook::default();
+        assert_eq!(parse_spell(&spell_book, "1"), Some(Spell::MAGIC_MISSILE));
+        assert_eq!(parse_spell(&spell_book, "5"), Some(Spell::RECHARGE));
+        assert_eq!(parse_spell(&spell_book, "poison"), Some(Spell::POISON));
+        assert_eq!(
+            parse_spell(&spell_book, "Magic Missile"),
+            Some(Spell::MAGIC_MISSILE)
+        );
+        assert_eq!(parse_spell(&spell_book, "0"), None);
+        assert_eq!(parse_spell(&spell_book, "6"), None);
+        assert_eq!(parse_spell(&spell_book, "fireball"), None);
+    }
+
+    #[test]
+    fn test_play() {
+        let spell_book = SpellBook::default();
+        let game = Game::new(
+            &spell_book,
+            Player::new(10, 250),
+            Boss::new(13, 8),
+            Difficulty::Normal,
+        );
+        assert_eq!(
+            play(game, "poison\n9\n1\n".as_bytes()),
+            Some((Winner::Player, 226))
+        );
+        assert_eq!(play(game, "4\n4\n".as_bytes()), None);
+        assert_eq!(play(game, "".as_bytes()), None);
+    }
+
+    #[test]
+    fn test_play_ends_before_cast() {
+        let spell_book = SpellBook::default();
+        // poison kills the boss at the start of the second player turn, before the missile
+        let game = Game::new(
+            &spell_book,
+            Player::new(10, 250),
+            Boss::new(6, 8),
+            Difficulty::Normal,
+        );
+        assert_eq!(
+            play(game, "poison\nmagic missile\n".as_bytes()),
+            Some((Winner::Player, 173))
+        );
+
+        // the player loses their last hit point before casting anything
+        let game = Game::new(
+            &spell_book,
+            Player::new(1, 250),
+            Boss::new(13, 8),
+            Difficulty::Hard,
+        );
+        assert_eq!(
+            play(game, "magic missile\n".as_bytes()),
+            Some((Winner::Boss, 0))
+        );
+    }
+
+    #[test]
+    fn test_play_without_castable_spells() {
+        let spell_book = SpellBook::default();
+        // not even a magic missile is affordable, so the game is lost without a prompt
+        let game = Game::new(
+            &spell_book,
+            Player::new(10, 52),
+            Boss::new(13, 8),
+            Difficulty::Normal,
+        );
+        assert_eq!(play(game, "".as_bytes()), Some((Winner::Boss, 0)));
+    }
+}
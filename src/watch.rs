@@ -0,0 +1,119 @@
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::challenges::{Challenges, DaySolutions, InputSource, SolveOptions};
+
+/// Solves `day` every time the modification time of the input file at `path` changes, printing
+/// how the answers differ from the previous run. Never returns
+pub fn watch(
+    challenges: &Challenges,
+    day: u8,
+    path: &Path,
+    options: &SolveOptions,
+    poll_interval: Duration,
+) -> ! {
+    let input = InputSource::File(path.to_path_buf());
+    // None before the first poll, Some(None) while the file can't be read
+    let mut last_modified: Option<Option<SystemTime>> = None;
+    let mut previous: Option<DaySolutions> = None;
+    println!("Watching {} for changes", path.display());
+    loop {
+        let modified = fs::metadata(path).and_then(|metadata| metadata.modified());
+        let current_modified = modified.as_ref().ok().copied();
+        if last_modified != Some(current_modified) {
+            last_modified = Some(current_modified);
+            match modified {
+                Ok(_) => {
+                    println!();
+                    println!("Solving day {}:", day);
+                    match challenges.solve(day, &input, options) {
+                        Ok(solutions) => {
+                            for line in describe_changes(previous.as_ref(), &solutions) {
+                                println!("  {}", line);
+                            }
+                            previous = Some(solutions);
+                        }
+                        Err(error) => eprintln!("error: {}", error),
+                    }
+                }
+                Err(error) => eprintln!("error: could not read {}: {}", path.display(), error),
+            }
+        }
+        thread::sleep(poll_interval);
+    }
+}
+
+/// One line per solved part with its answer and how it compares to the previous run
+fn describe_changes(previous: Option<&DaySolutions>, current: &DaySolutions) -> Vec<String> {
+    current
+        .parts()
+        .map(|(part, solution)| {
+            let previous_answer = previous
+                .and_then(|previous| previous.part(part))
+                .map(|solution| &solution.answer);
+            match (&solution.answer, previous_answer) {
+                (Err(error), _) => format!("{}: <failed> ({})", part, error),
+                (Ok(answer), None) => format!("{}: {}", part, answer),
+                (Ok(answer), Some(Ok(previous_answer))) if answer == previous_answer => {
+                    format!("{}: {} (unchanged)", part, answer)
+                }
+                (Ok(answer), Some(Ok(previous_answer))) => {
+                    format!("{}: {} (was {})", part, answer, previous_answer)
+                }
+                (Ok(answer), Some(Err(_))) => format!("{}: {} (was <failed>)", part, answer),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges::{ChallengeError, PartSolution, Timing};
+
+    fn solutions(part1: Result<&str, &str>, part2: Option<&str>) -> DaySolutions {
+        let timing = Timing {
+            repetitions: 1,
+            min: Duration::ZERO,
+            median: Duration::ZERO,
+            max: Duration::ZERO,
+        };
+        let solution = |answer: Result<&str, &str>| PartSolution {
+            answer: answer.map(str::to_owned).map_err(ChallengeError::new),
+            timing,
+            explanation: None,
+        };
+        DaySolutions {
+            parse_timing: timing,
+            part1: Some(solution(part1)),
+            part2: part2.map(|answer| solution(Ok(answer))),
+        }
+    }
+
+    #[test]
+    fn test_describe_changes() {
+        let first = solutions(Ok("3176"), Some("14710"));
+        assert_eq!(
+            describe_changes(None, &first),
+            vec!["part 1: 3176", "part 2: 14710"]
+        );
+
+        let second = solutions(Ok("3176"), Some("956"));
+        assert_eq!(
+            describe_changes(Some(&first), &second),
+            vec!["part 1: 3176 (unchanged)", "part 2: 956 (was 14710)"]
+        );
+
+        let third = solutions(Err("wire a has no value"), None);
+        assert_eq!(
+            describe_changes(Some(&second), &third),
+            vec!["part 1: <failed> (wire a has no value)"]
+        );
+        assert_eq!(
+            describe_changes(Some(&third), &second),
+            vec!["part 1: 3176 (was <failed>)", "part 2: 956"]
+        );
+    }
+}